@@ -1,21 +1,45 @@
-use chrono::{Datelike, Local};
-use clap::{Parser, ValueEnum};
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Local};
+use clap::{CommandFactory, FromArgMatches, Parser, error::ErrorKind, parser::ValueSource};
 use serde::{Serialize, Serializer};
-use strum_macros::EnumIter;
-use std::{fmt, process::Command};
-use anyhow::Result;
 use std::str::FromStr;
-use strum::IntoEnumIterator;
+use std::{
+    ffi::OsString,
+    fmt, fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
 
-use crate::{command, config::{Config, OptionalParameters}};
+use crate::{
+    audit::ExportFormat,
+    clock, command,
+    config::{Config, JavaScriptRuntime, OptionalParameters, Profile},
+    custom, dates,
+    matrix::MatrixFormat,
+    process,
+    scramble::ScrambleStrategy,
+    warnings::Warning,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, EnumIter)]
-#[clap(rename_all = "lowercase")] // ensure longer names like "CSharp" are used without any dashes ("csharp" instead of "c-sharp")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Language {
     Rust,
     CSharp,
     Java,
     Python,
+    Cpp,
+    TypeScript,
+    JavaScript,
+    Haskell,
+    Ruby,
+    Scala,
+    FSharp,
+    Julia,
+    OCaml,
+    // defined in the "languages" section of the config
+    Custom(&'static str),
 }
 
 // make sure the language enum is serialized lowercase
@@ -32,7 +56,10 @@ where
 impl fmt::Display for Language {
     // make the enum be formatted in all lowercase when converting to a string
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", format!("{:?}", self).to_lowercase())
+        match self {
+            Language::Custom(name) => write!(f, "{}", name),
+            _ => write!(f, "{}", format!("{:?}", self).to_lowercase()),
+        }
     }
 }
 
@@ -40,7 +67,7 @@ impl FromStr for Language {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        for lang in Language::iter() {
+        for lang in Language::all() {
             if lang.to_string() == s.to_lowercase() {
                 return Ok(lang);
             }
@@ -51,13 +78,111 @@ impl FromStr for Language {
 }
 
 impl Language {
+    pub const BUILTIN: [Language; 13] = [
+        Language::Rust,
+        Language::CSharp,
+        Language::Java,
+        Language::Python,
+        Language::Cpp,
+        Language::TypeScript,
+        Language::JavaScript,
+        Language::Haskell,
+        Language::Ruby,
+        Language::Scala,
+        Language::FSharp,
+        Language::Julia,
+        Language::OCaml,
+    ];
+
+    // the built-in languages followed by the ones defined in the config
+    pub fn all() -> Vec<Language> {
+        Language::BUILTIN
+            .into_iter()
+            .chain(custom::names().map(Language::Custom))
+            .collect()
+    }
+
+    // whether the directory contains a project of this language
+    pub fn is_project(&self, path: &Path) -> bool {
+        match *self {
+            Language::Rust => path.join("Cargo.toml").exists(),
+            Language::CSharp | Language::FSharp => {
+                let project_extension = if *self == Language::CSharp {
+                    "csproj"
+                } else {
+                    "fsproj"
+                };
+                fs::read_dir(path).is_ok_and(|entries| {
+                    entries.flatten().any(|entry| {
+                        entry
+                            .path()
+                            .extension()
+                            .is_some_and(|ext| ext == project_extension)
+                    })
+                })
+            }
+            Language::Java => path.join("Main.java").exists(),
+            Language::Python => path.join("main.py").exists(),
+            Language::Cpp => path.join("main.cpp").exists(),
+            Language::TypeScript => path.join("tsconfig.json").exists(),
+            Language::JavaScript => path.join("main.js").exists(),
+            Language::Haskell => path.join("Main.hs").exists(),
+            Language::Ruby => path.join("main.rb").exists(),
+            Language::Scala => path.join("project.scala").exists(),
+            Language::Julia => path.join("main.jl").exists(),
+            Language::OCaml => path.join("dune-project").exists(),
+            Language::Custom(name) => match &custom::get(name).detect {
+                Some(file) => path.join(file).exists(),
+                None => path.is_dir(),
+            },
+        }
+    }
+
+    // files written into a new project before its init command runs
+    pub fn init_files(&self, config: &Config) -> &'static [(&'static str, &'static str)] {
+        match *self {
+            Language::TypeScript => &[
+                ("package.json", "{\n  \"private\": true\n}\n"),
+                (
+                    "tsconfig.json",
+                    concat!(
+                        "{\n",
+                        "  \"compilerOptions\": {\n",
+                        "    \"target\": \"ES2022\",\n",
+                        "    \"module\": \"commonjs\",\n",
+                        "    \"strict\": true,\n",
+                        "    \"types\": [\"node\"],\n",
+                        "    \"rootDir\": \".\",\n",
+                        "    \"outDir\": \"dist\"\n",
+                        "  },\n",
+                        "  \"include\": [\"*.ts\"]\n",
+                        "}\n"
+                    ),
+                ),
+                ("main.ts", ""),
+            ],
+            Language::Scala => &[
+                ("project.scala", "//> using scala 3\n"),
+                ("Main.scala", "@main def main(): Unit =\n  ()\n"),
+            ],
+            Language::Julia if config.julia_project => &[("Project.toml", "[deps]\n")],
+            _ => &[],
+        }
+    }
+
     pub fn init_command(&self, config: &Config) -> Command {
         match *self {
             Language::Rust => command!("cargo", "init", "--bin", &config.project_path),
-            Language::CSharp => command!(
+            Language::CSharp | Language::FSharp => command!(
                 "dotnet",
                 "new",
                 "console",
+                "-lang",
+                if *self == Language::CSharp {
+                    "C#"
+                } else {
+                    "F#"
+                },
                 "--name",
                 &config.project_path.file_name().unwrap().to_str().unwrap(),
                 "--output",
@@ -65,122 +190,1435 @@ impl Language {
             ),
             Language::Java => command!("touch", &config.project_path.join("Main.java")),
             Language::Python => command!("touch", &config.project_path.join("main.py")),
+            Language::Cpp => command!("touch", &config.project_path.join("main.cpp")),
+            Language::TypeScript => {
+                let mut command =
+                    command!("npm", "install", "--save-dev", "typescript", "@types/node");
+                command.current_dir(&config.project_path);
+                command
+            }
+            Language::JavaScript => command!("touch", &config.project_path.join("main.js")),
+            Language::Ruby => command!("touch", &config.project_path.join("main.rb")),
+            Language::Julia => command!("touch", &config.project_path.join("main.jl")),
+            // creates the executable "bin/main.ml" along with a test and a library directory
+            Language::OCaml => command!("dune", "init", "project", "main", &config.project_path),
+            Language::Custom(name) => match &custom::get(name).init {
+                Some(init) => custom::command(init, config, self),
+                None => command!("mkdir", "-p", &config.project_path),
+            },
+            // the sources and the project configuration are written as init files
+            Language::Scala => command!("touch", &config.project_path.join("Main.scala")),
+            Language::Haskell => {
+                let mut command = command!(
+                    "cabal",
+                    "init",
+                    "--non-interactive",
+                    "--exe",
+                    "--application-dir=.",
+                    "--main-is=Main.hs"
+                );
+                command.current_dir(&config.project_path);
+                command
+            }
         }
     }
 
     pub fn build_command(&self, config: &Config) -> Option<Command> {
         match *self {
-            Language::Rust => Some(
-                command!(
+            Language::Rust => {
+                let mut command = command!(
                     "cargo",
                     "build",
-                    "--release",
                     "--manifest-path",
                     &config.project_path.join("Cargo.toml")
-                )
-            ),
-            Language::CSharp => Some(
-                command!(
-                    "dotnet",
-                    "build",
-                    &config.project_path
-                )
-            ),
-            Language::Java => Some(
-                command!(
+                );
+                command.args(Language::cargo_profile(config));
+                Some(command)
+            }
+            Language::CSharp | Language::FSharp => Some(command!(
+                "dotnet",
+                "build",
+                &config.project_path,
+                "--configuration",
+                Language::dotnet_configuration(config)
+            )),
+            Language::Java => {
+                let mut command = command!(
                     "javac",
+                    "-cp",
+                    Language::java_classpath(config),
                     &config.project_path.join("Main.java")
+                );
+                // all debugging information (instead of only line numbers and source files)
+                if config.profile == Profile::Debug {
+                    command.arg("-g");
+                }
+                Some(command)
+            }
+            Language::Python | Language::JavaScript | Language::Ruby | Language::Julia => None,
+            Language::Cpp => {
+                let mut command = command!(
+                    &config.cpp_compiler,
+                    "-std=c++20",
+                    match config.profile {
+                        Profile::Debug => "-O0",
+                        Profile::Release => "-O2",
+                    },
+                    "-o",
+                    &config.project_path.join("main"),
+                    &config.project_path.join("main.cpp")
+                );
+
+                if config.profile == Profile::Debug {
+                    command.arg("-g");
+                }
+                // make the headers of the common module includable
+                if let Some(common) = config.common_path(self) {
+                    command.arg("-I").arg(common);
+                }
+
+                Some(command)
+            }
+            Language::TypeScript => Some(command!("npx", "tsc", "--project", &config.project_path)),
+            Language::Haskell => {
+                let mut command = command!(
+                    "ghc",
+                    match config.profile {
+                        Profile::Debug => "-O0",
+                        Profile::Release => "-O2",
+                    },
+                    "-outputdir",
+                    &config.project_path.join("build"),
+                    "-o",
+                    &config.project_path.join("main"),
+                    &config.project_path.join("Main.hs")
+                );
+
+                // make the modules of the common library importable
+                if let Some(common) = config.common_path(self) {
+                    command.arg(format!("-i{}", common.display()));
+                }
+
+                Some(command)
+            }
+            Language::Scala => Some(Language::scala_cli(config, "compile")),
+            Language::OCaml => Some(command!(
+                "dune",
+                "build",
+                "--root",
+                &config.project_path,
+                "--profile",
+                Language::dune_profile(config)
+            )),
+            Language::Custom(name) => custom::get(name)
+                .build
+                .as_ref()
+                .map(|build| custom::command(build, config, self)),
+        }
+        .map(|mut command| {
+            command.current_dir(&config.project_path);
+            command.envs(config.language_env(self));
+            command
+        })
+    }
+
+    // why the build of the project isn't optimized, if it isn't (its timings would be misleading)
+    pub fn debug_build(&self, config: &Config) -> Option<String> {
+        match *self {
+            Language::Rust
+            | Language::CSharp
+            | Language::FSharp
+            | Language::Java
+            | Language::Cpp
+            | Language::Haskell
+            | Language::OCaml
+                if config.profile == Profile::Debug =>
+            {
+                Some("it's built with the debug profile".to_string())
+            }
+            // an overridden release profile
+            Language::Rust => {
+                let manifest = fs::read_to_string(config.project_path.join("Cargo.toml")).ok()?;
+                let mut release = false;
+
+                for line in manifest.lines().map(str::trim) {
+                    if line.starts_with('[') {
+                        release = line == "[profile.release]";
+                    } else if release
+                        && let Some((key, value)) = line.split_once('=')
+                        && key.trim() == "opt-level"
+                        && value.trim() == "0"
+                    {
+                        return Some("the release profile sets opt-level = 0".to_string());
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    // the file the build command produces (which the run command executes or loads), e.g. for
+    // profilers, debuggers or benchmarks of the exact build
+    pub fn artifact_path(&self, config: &Config) -> Result<PathBuf> {
+        let project = &config.project_path;
+        let executable = |path: PathBuf| match cfg!(windows) {
+            true => path.with_extension("exe"),
+            false => path,
+        };
+
+        let artifact = match *self {
+            // the binary is named after the package
+            Language::Rust => {
+                let manifest = fs::read_to_string(project.join("Cargo.toml"))
+                    .context("failed to read Cargo.toml")?;
+                let name = manifest
+                    .lines()
+                    .find_map(|line| {
+                        let (key, value) = line.split_once('=')?;
+                        (key.trim() == "name").then(|| value.trim().trim_matches('"').to_string())
+                    })
+                    .ok_or(anyhow!("the package name is missing in Cargo.toml"))?;
+                executable(
+                    project
+                        .join("target")
+                        .join(config.profile.to_string())
+                        .join(name),
                 )
-            ),
-            Language::Python => None,
-        }.map(|mut command| {
-            command.current_dir(&config.project_path); 
+            }
+            // the assembly is named after the project file and placed in a directory per framework
+            Language::CSharp | Language::FSharp => {
+                let name = project.file_name().unwrap().to_string_lossy().to_string();
+                let configuration = project
+                    .join("bin")
+                    .join(Language::dotnet_configuration(config));
+                fs::read_dir(&configuration)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|framework| framework.path().join(format!("{}.dll", name)))
+                    .find(|assembly| assembly.exists())
+                    .unwrap_or(configuration.join(format!("{}.dll", name)))
+            }
+            Language::Java => project.join("Main.class"),
+            Language::Cpp | Language::Haskell => executable(project.join("main")),
+            Language::TypeScript => project.join("dist").join("main.js"),
+            Language::OCaml => project
+                .join("_build")
+                .join("default")
+                .join("bin")
+                .join("main.exe"),
+            Language::Custom(name) => match &custom::get(name).artifact {
+                Some(artifact) => project.join(custom::expand(artifact, config, self)),
+                None => return Err(anyhow!("no artifact is configured for {}", self)),
+            },
+            // scala-cli keeps its build output in an internal cache
+            Language::Scala => return Err(anyhow!("scala-cli doesn't expose its build output")),
+            Language::Python | Language::JavaScript | Language::Ruby | Language::Julia => {
+                return Err(anyhow!("{} projects aren't built", self));
+            }
+        };
+
+        match artifact.exists() {
+            true => Ok(artifact),
+            false => Err(anyhow!(
+                "'{}' doesn't exist yet (run the solution to build it)",
+                artifact.display()
+            )),
+        }
+    }
+
+    // whether the path (relative to the project) is written by the build instead of being a source
+    pub fn is_build_output(&self, relative: &Path) -> bool {
+        let top = relative
+            .iter()
+            .next()
+            .and_then(|top| top.to_str())
+            .unwrap_or_default();
+        let executable = relative == Path::new("main") || relative == Path::new("main.exe");
+
+        match *self {
+            Language::Rust => top == "target",
+            Language::CSharp | Language::FSharp => top == "bin" || top == "obj",
+            Language::Java => relative
+                .extension()
+                .is_some_and(|extension| extension == "class"),
+            Language::Cpp => executable,
+            Language::Haskell => executable || top == "build",
+            Language::TypeScript => top == "dist" || top == "node_modules",
+            Language::OCaml => top == "_build",
+            Language::Python => top == "__pycache__",
+            Language::Custom(name) => custom::get(name)
+                .artifact
+                .as_ref()
+                .is_some_and(|artifact| relative == Path::new(artifact.trim_start_matches("./"))),
+            // scala-cli builds into a hidden directory
+            Language::Scala | Language::JavaScript | Language::Ruby | Language::Julia => false,
+        }
+    }
+
+    // runs the tests of the project (e.g. the examples of the puzzle), only rust has a standard test runner
+    pub fn test_command(&self, config: &Config) -> Option<Command> {
+        match *self {
+            Language::Rust => Some(command!(
+                "cargo",
+                "test",
+                "--manifest-path",
+                &config.project_path.join("Cargo.toml")
+            )),
+            Language::Scala => Some(Language::scala_cli(config, "test")),
+            Language::OCaml => Some(command!("dune", "test", "--root", &config.project_path)),
+            Language::Custom(name) => custom::get(name)
+                .test
+                .as_ref()
+                .map(|test| custom::command(test, config, self)),
+            Language::CSharp
+            | Language::FSharp
+            | Language::Java
+            | Language::Python
+            | Language::Cpp
+            | Language::TypeScript
+            | Language::JavaScript
+            | Language::Haskell
+            | Language::Ruby
+            | Language::Julia => None,
+        }
+        .map(|mut command| {
+            command.current_dir(&config.project_path);
+            command.envs(config.language_env(self));
             command
         })
     }
 
+    // downloads the dependencies declared by the project, so it can be built without network access
+    pub fn vendor_command(&self, config: &Config) -> Option<Command> {
+        let project = &config.project_path;
+
+        match *self {
+            Language::Rust => Some(command!(
+                "cargo",
+                "fetch",
+                "--manifest-path",
+                &project.join("Cargo.toml")
+            )),
+            Language::CSharp | Language::FSharp => Some(command!("dotnet", "restore", project)),
+            Language::Python => project.join("requirements.txt").exists().then(|| {
+                command!(
+                    "pip",
+                    "download",
+                    "--requirement",
+                    &project.join("requirements.txt"),
+                    "--dest",
+                    &project.join("vendor")
+                )
+            }),
+            Language::TypeScript => Some(command!("npm", "install", "--prefix", project)),
+            Language::JavaScript => project
+                .join("package.json")
+                .exists()
+                .then(|| command!("npm", "install", "--prefix", project)),
+            Language::Haskell => {
+                let mut command = command!("cabal", "build", "--only-dependencies");
+                command.current_dir(project);
+                Some(command)
+            }
+            Language::Ruby => project.join("Gemfile").exists().then(|| {
+                let mut command = command!("bundle", "cache");
+                command.current_dir(project);
+                command
+            }),
+            Language::Julia => project.join("Project.toml").exists().then(|| {
+                command!(
+                    "julia",
+                    format!("--project={}", project.display()),
+                    "--eval",
+                    "using Pkg; Pkg.instantiate()"
+                )
+            }),
+            Language::OCaml => {
+                let mut command = command!("opam", "install", ".", "--deps-only", "--yes");
+                command.current_dir(project);
+                Some(command)
+            }
+            // compiling fetches the dependencies declared by the using directives
+            Language::Scala => Some(Language::scala_cli(config, "compile")),
+            Language::Custom(name) => custom::get(name)
+                .vendor
+                .as_ref()
+                .map(|vendor| custom::command(vendor, config, self)),
+            // java and c++ projects have no dependency manifest
+            Language::Java | Language::Cpp => None,
+        }
+    }
+
+    // environment variables which limit the parallelism of common runtimes and libraries
+    pub fn threads_env(&self, threads: usize) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("AOC_THREADS", threads.to_string()),
+            ("OMP_NUM_THREADS", threads.to_string()),
+        ];
+
+        env.extend(match *self {
+            Language::Rust => vec![
+                ("RAYON_NUM_THREADS", threads.to_string()),
+                ("TOKIO_WORKER_THREADS", threads.to_string()),
+            ],
+            // dotnet parses its config knobs as hexadecimal numbers
+            Language::CSharp | Language::FSharp => vec![
+                ("DOTNET_PROCESSOR_COUNT", threads.to_string()),
+                ("DOTNET_ThreadPool_ForceMinWorkerThreads", format!("{:x}", threads)),
+                ("DOTNET_ThreadPool_ForceMaxWorkerThreads", format!("{:x}", threads)),
+            ],
+            // scala runs on the jvm as well
+            Language::Java | Language::Scala => vec![(
+                "JAVA_TOOL_OPTIONS",
+                format!(
+                    "-XX:ActiveProcessorCount={0} -Djava.util.concurrent.ForkJoinPool.common.parallelism={0}",
+                    threads
+                ),
+            )],
+            Language::Julia => vec![("JULIA_NUM_THREADS", threads.to_string())],
+            Language::OCaml | Language::Custom(_) => vec![],
+            Language::Python
+            | Language::Cpp
+            | Language::TypeScript
+            | Language::JavaScript
+            | Language::Haskell
+            | Language::Ruby => vec![],
+        });
+
+        env
+    }
+
+    // the arguments passed to the solution (instead of to the tool running it), e.g. the input
+    // path or the ones given after "--"
+    pub fn solution_args(&self, config: &Config, args: Vec<OsString>) -> Vec<OsString> {
+        match *self {
+            Language::Rust
+            | Language::CSharp
+            | Language::FSharp
+            | Language::Scala
+            | Language::OCaml
+                if self.direct_artifact(config).is_none() =>
+            {
+                [OsString::from("--")].into_iter().chain(args).collect()
+            }
+            _ => args,
+        }
+    }
+
+    // environment variables which remove sources of nondeterminism (hash seeds, parallelism, locale, timezone)
+    pub fn deterministic_env(&self) -> Vec<(&'static str, String)> {
+        let mut env = self.threads_env(1);
+
+        env.extend([
+            ("TZ", "UTC".to_string()),
+            ("LC_ALL", "C.UTF-8".to_string()),
+            ("LANG", "C.UTF-8".to_string()),
+        ]);
+
+        match *self {
+            Language::Rust
+            | Language::Cpp
+            | Language::TypeScript
+            | Language::JavaScript
+            | Language::Haskell
+            | Language::Ruby
+            | Language::Julia
+            | Language::OCaml
+            | Language::Custom(_) => {}
+            Language::CSharp | Language::FSharp => {
+                env.push(("DOTNET_SYSTEM_GLOBALIZATION_INVARIANT", "1".to_string()))
+            }
+            Language::Java | Language::Scala => {
+                if let Some((_, options)) = env
+                    .iter_mut()
+                    .find(|(name, _)| *name == "JAVA_TOOL_OPTIONS")
+                {
+                    options.push_str(" -Duser.timezone=UTC -Duser.language=en -Duser.country=US");
+                }
+            }
+            Language::Python => env.push(("PYTHONHASHSEED", "0".to_string())),
+        }
+
+        env
+    }
+
+    // the artifact of the build, which is run directly instead of through the tool which built it
+    // (that would add a startup overhead of its own to every run), as long as there is one
+    fn direct_artifact(&self, config: &Config) -> Option<PathBuf> {
+        match *self {
+            Language::Rust | Language::CSharp | Language::FSharp | Language::OCaml => {
+                self.artifact_path(config).ok()
+            }
+            _ => None,
+        }
+    }
+
     pub fn run_command(&self, config: &Config) -> Command {
-        let mut command = match *self {
-            Language::Rust => command!(
+        let mut command = match (*self, self.direct_artifact(config)) {
+            // the assembly is loaded by the dotnet host
+            (Language::CSharp | Language::FSharp, Some(assembly)) => command!("dotnet", assembly),
+            (_, Some(artifact)) => command!(artifact),
+            (language, None) => match language {
+                Language::Rust => {
+                    let mut command = command!(
+                        "cargo",
+                        "run",
+                        "--manifest-path",
+                        &config.project_path.join("Cargo.toml")
+                    );
+                    command.args(Language::cargo_profile(config));
+                    command
+                }
+                Language::CSharp | Language::FSharp => command!(
+                    "dotnet",
+                    "run",
+                    "--project",
+                    &config.project_path,
+                    "--configuration",
+                    Language::dotnet_configuration(config)
+                ),
+                Language::Java => command!("java", "-cp", Language::java_classpath(config), "Main"),
+                Language::Python => command!("python", &config.project_path.join("main.py")),
+                Language::Cpp | Language::Haskell => command!(&config.project_path.join("main")),
+                Language::Ruby => command!("ruby", "main.rb"),
+                Language::Scala => Language::scala_cli(config, "run"),
+                Language::Julia => command!("julia", "--project=.", "main.jl"),
+                Language::OCaml => command!(
+                    "dune",
+                    "exec",
+                    "--root",
+                    &config.project_path,
+                    "--profile",
+                    Language::dune_profile(config),
+                    "./bin/main.exe"
+                ),
+                Language::Custom(name) => custom::command(&custom::get(name).run, config, self),
+                Language::TypeScript => {
+                    command!("node", &config.project_path.join("dist").join("main.js"))
+                }
+                // deno is sandboxed and needs permissions to read the input and environment
+                // variables
+                Language::JavaScript => match config.javascript_runtime {
+                    JavaScriptRuntime::Node => {
+                        command!("node", &config.project_path.join("main.js"))
+                    }
+                    JavaScriptRuntime::Deno => command!(
+                        "deno",
+                        "run",
+                        "--allow-read",
+                        "--allow-env",
+                        &config.project_path.join("main.js")
+                    ),
+                    JavaScriptRuntime::Bun => {
+                        command!("bun", "run", &config.project_path.join("main.js"))
+                    }
+                },
+            },
+        };
+        command.current_dir(&config.project_path);
+        command.envs(config.language_env(self));
+
+        // make the common module importable
+        if *self == Language::Python
+            && let Some(common) = config.common_path(self)
+        {
+            command.env("PYTHONPATH", common);
+        }
+        if *self == Language::Ruby
+            && let Some(common) = config.common_path(self)
+        {
+            command.env("RUBYLIB", common);
+        }
+        // the trailing separator keeps the default load path (including the standard library)
+        if *self == Language::Julia
+            && let Some(common) = config.common_path(self)
+        {
+            let mut load_path = common.into_os_string();
+            load_path.push(if cfg!(windows) { ";" } else { ":" });
+            command.env("JULIA_LOAD_PATH", load_path);
+        }
+
+        command
+    }
+
+    // the flags selecting the profile of cargo, debug is its default
+    fn cargo_profile(config: &Config) -> Option<&'static str> {
+        (config.profile == Profile::Release).then_some("--release")
+    }
+
+    fn dotnet_configuration(config: &Config) -> &'static str {
+        match config.profile {
+            Profile::Debug => "Debug",
+            Profile::Release => "Release",
+        }
+    }
+
+    fn dune_profile(config: &Config) -> &'static str {
+        match config.profile {
+            Profile::Debug => "dev",
+            Profile::Release => "release",
+        }
+    }
+
+    // a scala-cli command on the project (and the common module, if configured)
+    fn scala_cli(config: &Config, subcommand: &str) -> Command {
+        let mut command = command!("scala-cli", subcommand, &config.project_path);
+
+        if let Some(common) = config.common_path(&Language::Scala) {
+            command.arg(common);
+        }
+
+        command
+    }
+
+    // the class path of java projects (including the common module, if configured)
+    fn java_classpath(config: &Config) -> OsString {
+        let mut classpath = OsString::from(&config.project_path);
+
+        if let Some(common) = config.common_path(&Language::Java) {
+            classpath.push(if cfg!(windows) { ";" } else { ":" });
+            classpath.push(common);
+        }
+
+        classpath
+    }
+
+    // wires a freshly initialized project to depend on the configured common module
+    pub fn link_common_command(&self, config: &Config) -> Option<Command> {
+        let common = config.common_path(self)?;
+
+        match *self {
+            Language::Rust => Some(command!(
                 "cargo",
-                "run",
+                "add",
+                "--path",
+                &common,
                 "--manifest-path",
                 &config.project_path.join("Cargo.toml")
-            ),
-            Language::CSharp => command!(
+            )),
+            Language::CSharp | Language::FSharp => Some(command!(
                 "dotnet",
-                "run",
-                "--project",
-                &config.project_path
-            ),
-            Language::Java => command!(
-                "java",
-                "-cp",
+                "add",
                 &config.project_path,
-                "Main"
-            ),
-            Language::Python => command!(
-                "python",
-                &config.project_path.join("main.py")
-            ),
-        };
-        command.current_dir(&config.project_path);
-        command
+                "reference",
+                &common
+            )),
+            // java, python, c++, haskell, ruby and julia pick up the common module through the class
+            // path, PYTHONPATH, include paths, RUBYLIB and JULIA_LOAD_PATH
+            Language::Java
+            | Language::Python
+            | Language::Cpp
+            | Language::Haskell
+            | Language::Ruby
+            | Language::Julia => None,
+            // scala-cli compiles the common module along with the project
+            Language::Scala => None,
+            // dune only builds libraries inside of the project (or installed ones)
+            Language::OCaml => None,
+            // custom commands can refer to the common module with "{{common}}"
+            Language::Custom(_) => None,
+            // javascript has no package manifest to add the common module to
+            Language::JavaScript => None,
+            Language::TypeScript => Some(command!(
+                "npm",
+                "install",
+                &common,
+                "--prefix",
+                &config.project_path
+            )),
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Mode {
     Run,
+    Submit,
+    Compare,
+    Perfstat,
+    Matrix,
+    Check,
     Init,
     Path,
     Code,
     Url,
+    Fetch,
+    Input,
+    Tag,
+    Retemplate,
+    Vendor,
+    List,
+    Stats,
+    Metrics,
+    Answers,
+    ImportHistory,
+    Art,
+    Countdown,
+    Cache,
+    Pending,
+    Login,
 }
 
-#[derive(Parser, Serialize)]
-pub struct Args {
+impl Mode {
+    // whether the mode downloads or submits anything with the cookie (if there is one)
+    pub fn uses_cookie(&self) -> bool {
+        matches!(
+            self,
+            Mode::Run
+                | Mode::Submit
+                | Mode::Compare
+                | Mode::Perfstat
+                | Mode::Matrix
+                | Mode::Init
+                | Mode::Fetch
+                | Mode::Stats
+                | Mode::ImportHistory
+                | Mode::Art
+                | Mode::Pending
+        )
+    }
+}
+
+const EXIT_CODES: &str = "Exit codes:
+  0    success (every submitted answer was correct)
+  1    a wrong answer (or failed checks)
+  2    the build of the solution failed
+  3    the solution failed or couldn't be started
+  4    any other error (e.g. of the network or the config)
+  5    some answers were queued instead of submitted
+  130  interrupted with ctrl-c";
+
+// the flags of every mode, accepted before and after it
+#[derive(clap::Args)]
+#[command(next_help_heading = "Global options")]
+pub struct GlobalArgs {
+    // a single year, a range ("2015-2024") or a list ("2020,2022") of years
+    #[arg(short = 'y', long = "year", global = true, value_parser = parse_years)]
+    pub years: Option<Years>,
+
+    // select every year from 2015 up to the latest one
+    #[arg(long, global = true, conflicts_with = "years")]
+    pub all_years: bool,
+
+    // a single day, a range ("1-10") or a list ("3,5,7") of days
+    #[arg(short = 'd', long = "day", global = true, value_parser = parse_days)]
+    pub days: Option<Days>,
+
+    // a single language or a list ("rust,python") of languages
     #[arg(
-        short,
-        long,
-        
-        // allow years from 2015 to current year (inclusive)
-        value_parser = clap::value_parser!(u16).range(2015..=(Local::now().year() as i64 - (Local::now().month() < 12) as i64))
+        short = 'l',
+        long = "language",
+        visible_alias = "languages",
+        global = true,
+        value_delimiter = ','
     )]
+    pub languages: Vec<Language>,
+
+    // print submission results as json instead of colored answers
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    // suppress a warning (by the id shown along with it), in addition to the ones of the config
+    #[arg(long, global = true, value_name = "WARNING", value_delimiter = ',')]
+    pub allow: Vec<Warning>,
+
+    // use the redo variant of the project, a second attempt next to the primary one in a "redo"
+    // directory (e.g. "2020/03/redo/rust", see init mode)
+    #[arg(long, global = true)]
+    pub redo: bool,
+
+    // run even if the selected day differs from the one of the current directory
+    #[arg(long, global = true)]
+    pub force: bool,
+
+    // behave as if invoked from this directory (e.g. from editor tasks or scripts)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub cwd: Option<PathBuf>,
+
+    // pretend the current time is the given one (for testing date dependent behavior outside of december)
+    #[arg(long, global = true, hide = true, value_name = "DATETIME", value_parser = clock::parse_datetime)]
+    pub fake_now: Option<DateTime<Local>>,
+}
+
+// the flags of the modes building and running the solution
+#[derive(clap::Args, Default)]
+pub struct ExecutionArgs {
+    // pin hash seeds, thread counts, locale and timezone to make runs reproducible
+    #[arg(long)]
+    pub deterministic: bool,
+
+    // limit the number of threads the solution should use (e.g. to compare benchmarks between machines)
+    #[arg(long, conflicts_with = "deterministic", value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+
+    // kill the solution if a run takes longer than this (e.g. "60s" or "2m"), instead of waiting
+    // for an accidental infinite loop forever
+    #[arg(long, value_parser = process::parse_timeout)]
+    pub timeout: Option<Duration>,
+
+    // run the solution this many times before the measured run
+    #[arg(long, default_value_t = 0)]
+    pub warmup: u32,
+
+    // build and run inside a temporary copy of the project
+    #[arg(long)]
+    pub isolated: bool,
+
+    // build with this profile instead of the configured one (e.g. debug for assertions)
+    #[arg(long, value_enum)]
+    pub profile: Option<Profile>,
+
+    // run the latest build as it is, without building the project first (unchanged projects
+    // aren't built again anyway)
+    #[arg(long)]
+    pub no_build: bool,
+
+    // arguments after "--" passed on to the solution, e.g. "aoc run -- --visualize"
+    #[arg(last = true, value_name = "SOLUTION_ARGS")]
+    pub solution_args: Vec<String>,
+}
+
+// the flags of the modes submitting answers
+#[derive(clap::Args, Default)]
+pub struct SubmissionArgs {
+    // refuse to submit answers contradicting earlier "too high" or "too low" hints (instead of
+    // only warning about them)
+    #[arg(long)]
+    pub strict: bool,
+
+    // submit answers without asking first
+    #[arg(long)]
+    pub yes: bool,
+
+    // queue rate limited answers instead of waiting for the cooldown and submitting them again
+    #[arg(long)]
+    pub no_retry: bool,
+}
+
+#[derive(clap::Args)]
+pub struct RunArgs {
+    // only take the answer of this part from the output and submit it
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    // run and parse the answers, but only print what would be submitted
+    #[arg(long)]
+    pub dry_run: bool,
+
+    // run on this file instead of the input of the day (e.g. an example), its answers are only
+    // submitted if it matches the official input
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub submission: SubmissionArgs,
+
+    #[command(flatten)]
+    pub execution: ExecutionArgs,
+}
+
+#[derive(clap::Args)]
+pub struct SubmitArgs {
+    // the part the answer is submitted as
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    // file with the answer of part 1 on the first line and the one of part 2 on the second,
+    // submitted instead of an answer given as a parameter
+    #[arg(long, value_name = "PATH")]
+    pub from_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub submission: SubmissionArgs,
+
+    #[arg(value_name = "ANSWER")]
+    pub answer: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub struct CompareArgs {
+    // a friend's answer manifest (file, directory or url) to compare against
+    #[arg(long, value_name = "DIR_OR_URL")]
+    pub against: Option<String>,
+
+    #[command(flatten)]
+    pub execution: ExecutionArgs,
+}
+
+#[derive(clap::Args)]
+pub struct MatrixArgs {
+    // how the grid of results is printed
+    #[arg(long, value_enum, default_value_t = MatrixFormat::Table)]
+    pub format: MatrixFormat,
+
+    #[command(flatten)]
+    pub execution: ExecutionArgs,
+}
+
+#[derive(clap::Args)]
+pub struct InitArgs {
+    // scaffold a solution which times both parts and prints them in the json protocol
+    #[arg(long)]
+    pub with_harness: bool,
+}
+
+#[derive(clap::Args)]
+pub struct PathArgs {
+    // print the path of the built solution instead of the project
+    #[arg(long)]
+    pub artifact: bool,
+}
+
+#[derive(clap::Args)]
+pub struct InputArgs {
+    // write a scrambled copy of the input to this file
+    #[arg(long, value_name = "PATH")]
+    pub scramble: Option<PathBuf>,
+
+    // transformations applied when scrambling the input
+    #[arg(long = "strategy", value_enum, value_delimiter = ',', default_values_t = [ScrambleStrategy::Digits, ScrambleStrategy::Letters])]
+    pub scramble_strategies: Vec<ScrambleStrategy>,
+
+    // seed for scrambling the input (random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(clap::Args)]
+pub struct TagArgs {
+    // remove the given tags instead of adding them
+    #[arg(long)]
+    pub remove: bool,
+
+    #[arg(value_name = "TAG")]
+    pub tags: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub struct ListArgs {
+    // only include projects with all of these tags
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub struct MetricsArgs {
+    // keep serving the metrics at http://<ADDRESS>/metrics instead of printing them once
+    #[arg(long, value_name = "ADDRESS")]
+    pub listen: Option<SocketAddr>,
+}
+
+#[derive(clap::Args)]
+pub struct AnswersArgs {
+    // print all submissions in this format instead of readable lines
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub export: Option<ExportFormat>,
+}
+
+#[derive(clap::Args)]
+pub struct ActionArgs {
+    // e.g. "aoc cache clear puzzle" or "aoc pending submit"
+    #[arg(value_name = "ACTION")]
+    pub action: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub struct LoginArgs {
+    // the value of the session cookie (asked for if not given)
+    pub cookie: Option<String>,
+}
+
+// every mode with its own flags
+#[derive(clap::Subcommand)]
+pub enum ModeArgs {
+    Run(RunArgs),
+    Submit(SubmitArgs),
+    Compare(CompareArgs),
+    Perfstat(ExecutionArgs),
+    Matrix(MatrixArgs),
+    Check,
+    Init(InitArgs),
+    Path(PathArgs),
+    Code,
+    Url,
+    Fetch,
+    Input(InputArgs),
+    Tag(TagArgs),
+    Retemplate,
+    Vendor,
+    List(ListArgs),
+    Stats,
+    Metrics(MetricsArgs),
+    Answers(AnswersArgs),
+    ImportHistory,
+    Art,
+    Countdown,
+    Cache(ActionArgs),
+    Pending(ActionArgs),
+    Login(LoginArgs),
+}
+
+// the command line, "aoc <mode> [flags]" like the positional mode of earlier versions, or only
+// the flags of run mode (e.g. "aoc --part 2")
+#[derive(Parser)]
+#[command(after_help = EXIT_CODES)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
+    #[command(subcommand)]
+    pub mode: Option<ModeArgs>,
+
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
+// the parsed command line, with the flags of modes other than the selected one at their defaults
+#[derive(Serialize)]
+pub struct Args {
+    #[serde(skip)]
+    pub years: Option<Years>,
+
+    #[serde(skip)]
+    pub all_years: bool,
+
+    // the year currently being worked on (one of the selected years)
     pub year: Option<u16>,
 
-    #[arg(
-        short,
-        long,
+    #[serde(skip)]
+    pub days: Option<Days>,
 
-        // allow days from 1 to 25 (inclusive)
-        value_parser = clap::value_parser!(u8).range(1..=25)
-    )]
+    // the day currently being worked on (one of the selected days)
     pub day: Option<u8>,
 
+    #[serde(skip)]
+    pub languages: Vec<Language>,
+
+    // the language currently being worked on (one of the selected languages)
     #[serde(serialize_with = "serialize_language")]
-    #[arg(short, long)]
     pub language: Option<Language>,
 
     #[serde(skip)]
-    #[arg(
-        value_enum,
-        default_value_t = Mode::Run
-    )]
+    pub json: bool,
+
+    #[serde(skip)]
+    pub allow: Vec<Warning>,
+
+    #[serde(skip)]
+    pub redo: bool,
+
+    #[serde(skip)]
+    pub force: bool,
+
+    #[serde(skip)]
+    pub cwd: Option<PathBuf>,
+
+    #[serde(skip)]
+    pub fake_now: Option<DateTime<Local>>,
+
+    #[serde(skip)]
     pub mode: Mode,
+
+    // run, compare, perfstat and matrix mode
+    #[serde(skip)]
+    pub execution: ExecutionArgs,
+
+    // run and submit mode
+    #[serde(skip)]
+    pub submission: SubmissionArgs,
+
+    // run and submit mode
+    #[serde(skip)]
+    pub part: Option<u8>,
+
+    // run mode
+    #[serde(skip)]
+    pub dry_run: bool,
+
+    #[serde(skip)]
+    pub input: Option<PathBuf>,
+
+    // submit mode
+    #[serde(skip)]
+    pub from_file: Option<PathBuf>,
+
+    // compare mode
+    #[serde(skip)]
+    pub against: Option<String>,
+
+    // matrix mode
+    #[serde(skip)]
+    pub format: MatrixFormat,
+
+    // init mode
+    #[serde(skip)]
+    pub with_harness: bool,
+
+    // path mode
+    #[serde(skip)]
+    pub artifact: bool,
+
+    // input mode
+    #[serde(skip)]
+    pub scramble: Option<PathBuf>,
+
+    #[serde(skip)]
+    pub scramble_strategies: Vec<ScrambleStrategy>,
+
+    #[serde(skip)]
+    pub seed: Option<u64>,
+
+    // tag mode
+    #[serde(skip)]
+    pub remove: bool,
+
+    // list mode
+    #[serde(skip)]
+    pub tags: Vec<String>,
+
+    // metrics mode
+    #[serde(skip)]
+    pub listen: Option<SocketAddr>,
+
+    // answers mode
+    #[serde(skip)]
+    pub export: Option<ExportFormat>,
+
+    // mode specific parameters (e.g. the answer of submit mode or the action of cache mode)
+    #[serde(skip)]
+    pub params: Vec<String>,
+}
+
+impl Args {
+    // parses the command line of the process, the flags of run mode are only accepted without a
+    // mode (or after it), as they'd be ignored in front of another mode
+    pub fn try_parse() -> Result<Self, clap::Error> {
+        let mut command = Cli::command();
+        let matches = command.try_get_matches_from_mut(std::env::args_os())?;
+
+        let misplaced = command
+            .get_arguments()
+            .find(|arg| {
+                !arg.is_global_set()
+                    && matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+            })
+            .and_then(|arg| arg.get_long())
+            .map(str::to_string);
+        if let (Some(mode), Some(flag)) = (matches.subcommand_name(), misplaced) {
+            return Err(command.error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "'--{}' has to follow the mode ('aoc {} --{} ...')",
+                    flag, mode, flag
+                ),
+            ));
+        }
+
+        Cli::from_arg_matches(&matches).map(Args::from)
+    }
+}
+
+impl From<Cli> for Args {
+    fn from(cli: Cli) -> Self {
+        let GlobalArgs {
+            years,
+            all_years,
+            days,
+            languages,
+            json,
+            allow,
+            redo,
+            force,
+            cwd,
+            fake_now,
+        } = cli.global;
+
+        let mut args = Args {
+            years,
+            all_years,
+            year: None,
+            days,
+            day: None,
+            languages,
+            language: None,
+            json,
+            allow,
+            redo,
+            force,
+            cwd,
+            fake_now,
+            mode: Mode::Run,
+            execution: ExecutionArgs::default(),
+            submission: SubmissionArgs::default(),
+            part: None,
+            dry_run: false,
+            input: None,
+            from_file: None,
+            against: None,
+            format: MatrixFormat::Table,
+            with_harness: false,
+            artifact: false,
+            scramble: None,
+            scramble_strategies: vec![ScrambleStrategy::Digits, ScrambleStrategy::Letters],
+            seed: None,
+            remove: false,
+            tags: Vec::new(),
+            listen: None,
+            export: None,
+            params: Vec::new(),
+        };
+
+        args.mode = match cli.mode.unwrap_or(ModeArgs::Run(cli.run)) {
+            ModeArgs::Run(run) => {
+                args.part = run.part;
+                args.dry_run = run.dry_run;
+                args.input = run.input;
+                args.submission = run.submission;
+                args.execution = run.execution;
+                Mode::Run
+            }
+            ModeArgs::Submit(submit) => {
+                args.part = submit.part;
+                args.from_file = submit.from_file;
+                args.submission = submit.submission;
+                args.params = submit.answer;
+                Mode::Submit
+            }
+            ModeArgs::Compare(compare) => {
+                args.against = compare.against;
+                args.execution = compare.execution;
+                Mode::Compare
+            }
+            ModeArgs::Perfstat(execution) => {
+                args.execution = execution;
+                Mode::Perfstat
+            }
+            ModeArgs::Matrix(matrix) => {
+                args.format = matrix.format;
+                args.execution = matrix.execution;
+                Mode::Matrix
+            }
+            ModeArgs::Check => Mode::Check,
+            ModeArgs::Init(init) => {
+                args.with_harness = init.with_harness;
+                Mode::Init
+            }
+            ModeArgs::Path(path) => {
+                args.artifact = path.artifact;
+                Mode::Path
+            }
+            ModeArgs::Code => Mode::Code,
+            ModeArgs::Url => Mode::Url,
+            ModeArgs::Fetch => Mode::Fetch,
+            ModeArgs::Input(input) => {
+                args.scramble = input.scramble;
+                args.scramble_strategies = input.scramble_strategies;
+                args.seed = input.seed;
+                Mode::Input
+            }
+            ModeArgs::Tag(tag) => {
+                args.remove = tag.remove;
+                args.params = tag.tags;
+                Mode::Tag
+            }
+            ModeArgs::Retemplate => Mode::Retemplate,
+            ModeArgs::Vendor => Mode::Vendor,
+            ModeArgs::List(list) => {
+                args.tags = list.tags;
+                Mode::List
+            }
+            ModeArgs::Stats => Mode::Stats,
+            ModeArgs::Metrics(metrics) => {
+                args.listen = metrics.listen;
+                Mode::Metrics
+            }
+            ModeArgs::Answers(answers) => {
+                args.export = answers.export;
+                Mode::Answers
+            }
+            ModeArgs::ImportHistory => Mode::ImportHistory,
+            ModeArgs::Art => Mode::Art,
+            ModeArgs::Countdown => Mode::Countdown,
+            ModeArgs::Cache(cache) => {
+                args.params = cache.action;
+                Mode::Cache
+            }
+            ModeArgs::Pending(pending) => {
+                args.params = pending.action;
+                Mode::Pending
+            }
+            ModeArgs::Login(login) => {
+                args.params = login.cookie.into_iter().collect();
+                Mode::Login
+            }
+        };
+
+        args
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Years(pub Vec<u16>);
+
+#[derive(Debug, Clone)]
+pub struct Days(pub Vec<u8>);
+
+// the most recent year with puzzles
+pub fn latest_year() -> u16 {
+    dates::latest_year(clock::now(), &dates::aoc_timezone())
+}
+
+// parses a comma separated list of numbers and inclusive ranges (e.g. "1-3,7") within the given bounds
+fn parse_selection(s: &str, min: u16, max: u16) -> Result<Vec<u16>, String> {
+    let parse = |value: &str| -> Result<u16, String> {
+        let value: u16 = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid number: {}", value))?;
+
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(format!("{} is not in {}..={}", value, min, max))
+        }
+    };
+
+    let mut values = Vec::new();
+
+    for item in s.split(',') {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("invalid range: {}", item));
+                }
+                values.extend(start..=end);
+            }
+            None => values.push(parse(item)?),
+        }
+    }
+
+    values.sort();
+    values.dedup();
+
+    Ok(values)
+}
+
+// allow years from 2015 on, the latest year is only known once the clock has been set (see
+// Args::build)
+fn parse_years(s: &str) -> Result<Years, String> {
+    Ok(Years(parse_selection(s, 2015, u16::MAX)?))
+}
+
+// allow days from 1 to 25 (inclusive)
+fn parse_days(s: &str) -> Result<Days, String> {
+    Ok(Days(
+        parse_selection(s, 1, 25)?
+            .into_iter()
+            .map(|day| day as u8)
+            .collect(),
+    ))
 }
 
 impl Args {
-    pub fn build(&mut self, optional_parameters: OptionalParameters) {
-        self.year = self.year
-            .or(optional_parameters.year)
-            .or(Some(Local::now().year() as u16 - (Local::now().month() < 12) as u16)); // default to current year, if month is december, else previous year
+    // describes how the explicitly selected year or day contradicts the current directory
+    // (e.g. "--day 6" from within the project of day 7)
+    pub fn directory_mismatch(&self, optional_parameters: &OptionalParameters) -> Option<String> {
+        let year_mismatch = match (&self.years, optional_parameters.year) {
+            (Some(years), Some(year)) => !years.0.contains(&year),
+            _ => false,
+        };
+        let day_mismatch = match (&self.days, optional_parameters.day) {
+            (Some(days), Some(day)) => !days.0.contains(&day),
+            _ => false,
+        };
+
+        let mut directory = Vec::new();
+        if let Some(year) = optional_parameters.year {
+            directory.push(year.to_string());
+        }
+        if let Some(day) = optional_parameters.day {
+            directory.push(format!("{:0>2}", day));
+        }
+
+        let join = |values: Vec<String>| values.join(", ");
+        let selection = match (&self.years, &self.days) {
+            (_, Some(days)) if day_mismatch => {
+                format!(
+                    "day {}",
+                    join(days.0.iter().map(|d| d.to_string()).collect())
+                )
+            }
+            (Some(years), _) => {
+                format!(
+                    "year {}",
+                    join(years.0.iter().map(|y| y.to_string()).collect())
+                )
+            }
+            _ => return None,
+        };
 
-        self.day = self.day
-            .or(optional_parameters.day)
-            .or(Some(if Local::now().month() == 12 { Local::now().day() as u8 } else { 1 })); // default to current day, if month is december, else 1
+        (year_mismatch || day_mismatch).then(|| {
+            format!(
+                "the current directory belongs to {}, but the selection is {}",
+                directory.join("/"),
+                selection
+            )
+        })
+    }
+
+    // fills in missing parameters and returns the selected years and days
+    pub fn build(
+        &mut self,
+        optional_parameters: OptionalParameters,
+    ) -> Result<(Vec<u16>, Vec<u8>)> {
+        let (default_year, default_day) =
+            dates::default_puzzle(clock::now(), &dates::aoc_timezone());
 
-        self.language = self.language
+        // years up to the current one (inclusive) can be selected
+        let latest = latest_year();
+        if let Some(year) = self
+            .years
+            .iter()
+            .flat_map(|years| &years.0)
+            .find(|&&year| year > latest)
+        {
+            return Err(anyhow!(
+                "invalid value for '--year': {} is not in 2015..={}",
+                year,
+                latest
+            ));
+        }
+
+        let years = if self.all_years {
+            (2015..=latest_year()).collect()
+        } else {
+            self.years
+                .clone()
+                .map(|years| years.0)
+                .or(optional_parameters.year.map(|year| vec![year]))
+                .unwrap_or(vec![default_year])
+        };
+
+        self.year = years.first().copied();
+
+        let days = self
+            .days
+            .clone()
+            .map(|days| days.0)
+            .or(optional_parameters.day.map(|day| vec![day]))
+            .unwrap_or(vec![default_day]);
+
+        self.day = days.first().copied();
+
+        self.language = self
+            .languages
+            .first()
+            .copied()
             .or(optional_parameters.language);
+
+        Ok((years, days))
     }
 }
@@ -0,0 +1,125 @@
+use anyhow::Result;
+use colored::Colorize;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::{
+    cache::{self, Cache, Resource},
+    config::Config,
+    urls, web,
+};
+
+// the overview page of a year containing the calendar, preferring a cached copy
+pub async fn fetch_calendar(config: &Config, cookie: &str, year: u16) -> Result<String> {
+    let cache = Cache::open(&config.cache)?;
+    let key = cache::account_key(cookie, &format!("calendar-{}", year));
+
+    match cache.get(Resource::Completion, &key) {
+        Some(page) => Ok(page),
+        None => {
+            let page = web::get(cookie, &urls::calendar(year)).await?;
+            cache.put(Resource::Completion, &key, &page)?;
+            Ok(page)
+        }
+    }
+}
+
+// the earned stars per day according to the calendar
+pub fn parse_day_stars(page: &str) -> HashMap<u8, u8> {
+    Regex::new(r#"aria-label="Day (?P<day>\d+)(?P<stars>, one star|, two stars)?""#)
+        .unwrap()
+        .captures_iter(page)
+        .filter_map(|captures| {
+            let stars = match captures.name("stars").map(|s| s.as_str()) {
+                Some(", two stars") => 2,
+                Some(_) => 1,
+                None => 0,
+            };
+            Some((captures["day"].parse().ok()?, stars))
+        })
+        .collect()
+}
+
+// decodes the few html entities used within the calendar (and the titles of puzzles)
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(html: &str) -> String {
+    decode_entities(&Regex::new(r"<[^>]*>").unwrap().replace_all(html, ""))
+}
+
+// renders the calendar of a year's overview page as colored ascii art
+// (days with two stars are bright, days with one star are normal, other days are dimmed)
+pub fn render_calendar(page: &str) -> Option<String> {
+    let calendar = Regex::new(r#"(?s)<pre class="calendar[^"]*">(.*?)</pre>"#)
+        .unwrap()
+        .captures(page)?
+        .get(1)?
+        .as_str();
+
+    let day_re = Regex::new(
+        r#"(?s)<a [^>]*class="calendar-day\d+(?P<status> calendar-complete| calendar-verycomplete)?"[^>]*>(?P<content>.*?)</a>"#,
+    )
+    .unwrap();
+    let mark_re =
+        Regex::new(r#"<span class="calendar-mark-(complete|verycomplete)">\*</span>"#).unwrap();
+
+    let mut rendered = String::new();
+    let mut previous = 0;
+
+    for captures in day_re.captures_iter(calendar) {
+        let whole = captures.get(0).unwrap();
+        rendered.push_str(
+            &strip_tags(&calendar[previous..whole.start()])
+                .bright_black()
+                .to_string(),
+        );
+
+        let status = captures.name("status").map(|s| s.as_str().trim());
+        let earned = match status {
+            Some("calendar-verycomplete") => 2,
+            Some("calendar-complete") => 1,
+            _ => 0,
+        };
+
+        let color = |text: &str| match earned {
+            2 => text.bright_white(),
+            1 => text.white(),
+            _ => text.bright_black(),
+        };
+
+        // the star marks are hidden by css in the browser, so only render the actually earned ones
+        let content = &captures["content"];
+        let mut content_previous = 0;
+
+        for mark in mark_re.captures_iter(content) {
+            let whole_mark = mark.get(0).unwrap();
+            let required = if &mark[1] == "complete" { 1 } else { 2 };
+
+            rendered.push_str(
+                &color(&strip_tags(&content[content_previous..whole_mark.start()])).to_string(),
+            );
+            rendered.push_str(
+                &if earned >= required {
+                    "*".bright_yellow().bold()
+                } else {
+                    " ".normal()
+                }
+                .to_string(),
+            );
+            content_previous = whole_mark.end();
+        }
+
+        rendered.push_str(&color(&strip_tags(&content[content_previous..])).to_string());
+        previous = whole.end();
+    }
+
+    rendered.push_str(&strip_tags(&calendar[previous..]).bright_black().to_string());
+
+    Some(rendered)
+}
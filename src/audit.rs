@@ -0,0 +1,123 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{config::Config, dates, history::SubmissionHistory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+// a single judged submission of the history
+#[derive(Serialize)]
+struct Entry {
+    year: u16,
+    day: u8,
+    part: u8,
+    by: String,
+    answer: String,
+    verdict: &'static str,
+    submitted_at: String,
+}
+
+const CSV_HEADER: &str = "year,day,part,by,answer,verdict,submitted_at";
+
+// quotes a csv field if necessary (doubling the quotes inside of it)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// every submission of the selected years (of all members, including the answers judged before the
+// last one), the oldest one first per part and member
+fn entries(config: &Config, years: &[u16]) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+
+    for &year in years {
+        for day in 1..=dates::last_day(year) {
+            let history = SubmissionHistory::load(&config.history, year, day)?;
+
+            for record in &history.submissions {
+                let entry = |answer: &str, verdict, submitted_at: &str| Entry {
+                    year,
+                    day,
+                    part: record.part,
+                    by: record.by.clone(),
+                    answer: answer.to_string(),
+                    verdict,
+                    submitted_at: submitted_at.to_string(),
+                };
+
+                for judged in &record.earlier {
+                    entries.push(entry(
+                        &judged.answer,
+                        judged.verdict.label(),
+                        &judged.submitted_at,
+                    ));
+                }
+                entries.push(entry(
+                    &record.answer,
+                    record.verdict.label(),
+                    &record.submitted_at,
+                ));
+            }
+        }
+    }
+
+    entries.sort_by_key(|entry| (entry.year, entry.day, entry.part));
+    Ok(entries)
+}
+
+// the submissions of the selected years in the given format
+pub fn export(config: &Config, years: &[u16], format: ExportFormat) -> Result<String> {
+    let entries = entries(config, years)?;
+
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(&entries)? + "\n"),
+        ExportFormat::Csv => {
+            let mut csv = format!("{}\n", CSV_HEADER);
+            for entry in &entries {
+                let fields = [
+                    entry.year.to_string(),
+                    entry.day.to_string(),
+                    entry.part.to_string(),
+                    csv_field(&entry.by),
+                    csv_field(&entry.answer),
+                    entry.verdict.to_string(),
+                    entry.submitted_at.clone(),
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            Ok(csv)
+        }
+    }
+}
+
+// the submissions of the selected years as readable lines, e.g.
+// "2020/03 part 1 (me): '42' correct at 2020-12-03T06:00:00+00:00"
+pub fn list(config: &Config, years: &[u16]) -> Result<String> {
+    Ok(entries(config, years)?
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}/{:0>2} part {} ({}): '{}' {} at {}\n",
+                entry.year,
+                entry.day,
+                entry.part,
+                entry.by,
+                entry.answer,
+                entry.verdict.replace('_', " "),
+                match entry.submitted_at.as_str() {
+                    "" => "an unknown time",
+                    submitted_at => submitted_at,
+                }
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,240 @@
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{paths, storage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[serde(rename_all = "lowercase")]
+pub enum Resource {
+    Input,
+    Puzzle,
+    Completion,
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
+
+impl Resource {
+    // name of the subdirectory the resource is stored in
+    fn dir_name(&self) -> &'static str {
+        match *self {
+            Resource::Input => "inputs",
+            Resource::Puzzle => "puzzles",
+            Resource::Completion => "completion",
+        }
+    }
+
+    // inputs never change once published, everything else can go stale
+    fn default_ttl(&self) -> Option<Duration> {
+        match *self {
+            Resource::Input => None,
+            Resource::Puzzle => Some(Duration::from_secs(60 * 60)),
+            Resource::Completion => Some(Duration::from_secs(15 * 60)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CacheConfig {
+    // maximum size of the cache directory in bytes
+    pub max_size: u64,
+    // per-resource time to live in seconds (0 means the resource never expires)
+    pub ttl: HashMap<Resource, u64>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 64 * 1024 * 1024,
+            ttl: HashMap::new(),
+        }
+    }
+}
+
+// the key of a resource which differs per account (e.g. the input), so that the entries of another
+// session cookie (like the one before logging in with another account) aren't used
+pub fn account_key(cookie: &str, key: &str) -> String {
+    format!("{}-{}", key, storage::hash(cookie.as_bytes()))
+}
+
+pub struct ResourceStats {
+    pub resource: Resource,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+pub struct Cache<'a> {
+    root: PathBuf,
+    config: &'a CacheConfig,
+}
+
+impl<'a> Cache<'a> {
+    pub fn open(config: &'a CacheConfig) -> Result<Self> {
+        let root = paths::cache_dir()?;
+
+        Ok(Self { root, config })
+    }
+
+    fn ttl(&self, resource: Resource) -> Option<Duration> {
+        match self.config.ttl.get(&resource) {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(*seconds)),
+            None => resource.default_ttl(),
+        }
+    }
+
+    fn entry_path(&self, resource: Resource, key: &str) -> PathBuf {
+        self.root.join(resource.dir_name()).join(key)
+    }
+
+    // returns the cached content if it exists and has not expired yet
+    pub fn get(&self, resource: Resource, key: &str) -> Option<String> {
+        let path = self.entry_path(resource, key);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+
+        if let Some(ttl) = self.ttl(resource)
+            && SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default()
+                > ttl
+        {
+            return None;
+        }
+
+        fs::read_to_string(&path).ok()
+    }
+
+    pub fn put(&self, resource: Resource, key: &str, content: &str) -> Result<()> {
+        let path = self.entry_path(resource, key);
+        fs::create_dir_all(path.parent().unwrap()).with_context(|| {
+            format!("failed to create cache directory '{}'", self.root.display())
+        })?;
+        storage::write(&path, content)
+            .with_context(|| format!("failed to write cache entry '{}'", path.display()))?;
+
+        self.evict()
+    }
+
+    // all cache entries as (resource, path, size, modified)
+    fn entries(&self) -> Vec<(Resource, PathBuf, u64, SystemTime)> {
+        Resource::iter()
+            .flat_map(|resource| {
+                fs::read_dir(self.root.join(resource.dir_name()))
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| entry.ok())
+                    .filter_map(move |entry| {
+                        let metadata = entry.metadata().ok()?;
+                        metadata.is_file().then(|| {
+                            (
+                                resource,
+                                entry.path(),
+                                metadata.len(),
+                                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                            )
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    // remove the oldest entries until the cache fits into the configured maximum size
+    fn evict(&self) -> Result<()> {
+        let mut entries = self.entries();
+        let mut total: u64 = entries.iter().map(|(_, _, size, _)| size).sum();
+
+        entries.sort_by_key(|(_, _, _, modified)| *modified);
+
+        for (_, path, size, _) in entries {
+            if total <= self.config.max_size {
+                break;
+            }
+
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to evict cache entry '{}'", path.display()))?;
+            total -= size;
+        }
+
+        Ok(())
+    }
+
+    // removes all entries of the given resource (or the whole cache) and returns the amount of freed bytes
+    pub fn clear(&self, resource: Option<Resource>) -> Result<u64> {
+        let mut freed = 0;
+
+        for (_, path, size, _) in self
+            .entries()
+            .into_iter()
+            .filter(|(r, _, _, _)| resource.is_none_or(|resource| *r == resource))
+        {
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to remove cache entry '{}'", path.display()))?;
+            freed += size;
+        }
+
+        Ok(freed)
+    }
+
+    pub fn stats(&self) -> Vec<ResourceStats> {
+        let entries = self.entries();
+
+        Resource::iter()
+            .map(|resource| {
+                let matching = entries.iter().filter(|(r, _, _, _)| *r == resource);
+                ResourceStats {
+                    resource,
+                    files: matching.clone().count(),
+                    bytes: matching.map(|(_, _, size, _)| size).sum(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    pub fn max_size(&self) -> u64 {
+        self.config.max_size
+    }
+}
+
+impl std::str::FromStr for Resource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Resource::iter()
+            .find(|resource| resource.to_string() == s.to_lowercase())
+            .ok_or_else(|| anyhow!("unknown cache resource: {}", s))
+    }
+}
+
+// format a byte count in a human readable way
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, units[unit])
+    } else {
+        format!("{:.1} {}", value, units[unit])
+    }
+}
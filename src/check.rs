@@ -0,0 +1,184 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::{path::Path, process::Command};
+
+use crate::{
+    args::Args,
+    command,
+    config::Config,
+    dates,
+    history::SubmissionHistory,
+    metadata::{Metadata, ProjectState},
+    theme,
+};
+
+enum Status {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+// the result of a command as a check status (failures include the first lines of its error output)
+fn command_status(mut command: Command) -> Result<Status> {
+    let output = command.output()?;
+
+    if output.status.success() {
+        return Ok(Status::Passed);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(Status::Failed(
+        stderr
+            .trim_end()
+            .lines()
+            .take(5)
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    ))
+}
+
+fn check_answers(config: &Config, args: &Args) -> Result<Status> {
+    let (year, day) = (args.year.unwrap(), args.day.unwrap());
+    let history = SubmissionHistory::load(&config.history, year, day)?;
+
+    // the last day only has a single puzzle to solve
+    let parts = if day == dates::last_day(year) { 1 } else { 2 };
+    let missing: Vec<String> = (1..=parts)
+        .filter(|part| {
+            !history
+                .get(*part)
+                .is_some_and(|record| record.verdict.is_correct())
+        })
+        .map(|part| part.to_string())
+        .collect();
+
+    Ok(match missing[..] {
+        [] => Status::Passed,
+        _ => Status::Failed(format!(
+            "no correct answer recorded for part {}",
+            missing.join(", ")
+        )),
+    })
+}
+
+fn check_metadata(config: &Config) -> Result<Status> {
+    let metadata = match Metadata::load(&config.project_path) {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(Status::Failed(format!("{:#}", e))),
+    };
+
+    let mut problems = Vec::new();
+
+    if let Err(e) = metadata.extractor(config) {
+        problems.push(e.to_string());
+    }
+
+    match ProjectState::load(&config.project_path) {
+        Ok(ProjectState {
+            last_run: Some(run),
+            ..
+        }) if !Path::new(&run.input).exists() => {
+            problems.push(format!(
+                "the input of the last run '{}' does not exist",
+                run.input
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => problems.push(format!("{:#}", e)),
+    }
+
+    let mut tags = metadata.tags.clone();
+    tags.sort();
+    tags.dedup();
+    if tags != metadata.tags || tags.iter().any(|tag| *tag != tag.to_lowercase()) {
+        problems.push("tags are not lowercase, sorted and unique".to_string());
+    }
+
+    Ok(match problems[..] {
+        [] => Status::Passed,
+        _ => Status::Failed(problems.join("\n")),
+    })
+}
+
+fn check_input_not_committed(config: &Config, args: &Args) -> Result<Status> {
+    let input_file = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+    let Some(parent) = input_file.parent().filter(|parent| parent.exists()) else {
+        return Ok(Status::Skipped("there is no input".to_string()));
+    };
+
+    let mut inside = command!("git", "rev-parse", "--is-inside-work-tree");
+    if !inside.current_dir(parent).output()?.status.success() {
+        return Ok(Status::Skipped("not inside a git repository".to_string()));
+    }
+
+    // puzzle inputs must not be redistributed, so they shouldn't be part of the repository
+    let mut tracked = command!("git", "ls-files", "--error-unmatch", &input_file);
+    Ok(
+        match tracked.current_dir(parent).output()?.status.success() {
+            true => Status::Failed(format!("'{}' is tracked by git", input_file.display())),
+            false => Status::Passed,
+        },
+    )
+}
+
+// checks the project of the day before pushing it and returns whether all checks passed
+pub fn run(config: &Config, args: &Args) -> Result<bool> {
+    let language = args.language.unwrap();
+    // broken metadata is reported by its own check
+    let metadata = Metadata::load(&config.project_path).unwrap_or_default();
+    let puzzle_env = config.puzzle_env(args.year.unwrap(), args.day.unwrap())?;
+
+    let checks = [
+        (
+            "builds",
+            match metadata.build_command(config, &language) {
+                Some(mut command) => {
+                    command.envs(puzzle_env.clone());
+                    command_status(command)?
+                }
+                None => Status::Skipped(format!("{} has no build step", language)),
+            },
+        ),
+        (
+            "tests pass",
+            match language.test_command(config) {
+                Some(mut command) => {
+                    command.envs(puzzle_env);
+                    command_status(command)?
+                }
+                None => Status::Skipped(format!("no test command for {}", language)),
+            },
+        ),
+        ("answers recorded", check_answers(config, args)?),
+        ("metadata consistent", check_metadata(config)?),
+        (
+            "input not committed",
+            check_input_not_committed(config, args)?,
+        ),
+    ];
+
+    let mut passed = true;
+
+    for (name, status) in checks {
+        match status {
+            Status::Passed => println!("{} {}", theme::correct_symbol(), name),
+            Status::Failed(reason) => {
+                passed = false;
+                println!("{} {}", theme::incorrect_symbol(), name);
+                for line in reason.lines() {
+                    println!("    {}", line.dimmed());
+                }
+            }
+            Status::Skipped(reason) => {
+                println!(
+                    "{} {} {}",
+                    theme::skipped_symbol(),
+                    name,
+                    format!("({})", reason).dimmed()
+                )
+            }
+        }
+    }
+
+    Ok(passed)
+}
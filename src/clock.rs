@@ -0,0 +1,33 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, TimeZone};
+use std::sync::OnceLock;
+
+// difference between the faked and the real time (the faked clock keeps ticking)
+static OFFSET: OnceLock<TimeDelta> = OnceLock::new();
+
+// the current time, shifted if a fake time has been set
+pub fn now() -> DateTime<Local> {
+    Local::now() + OFFSET.get().copied().unwrap_or_default()
+}
+
+// pretends the current time is the given one for the rest of the process
+pub fn fake_now(now: DateTime<Local>) {
+    let _ = OFFSET.set(now - Local::now());
+}
+
+// parses an rfc 3339 datetime ("2024-12-01T05:59:50+00:00") or a local one ("2024-12-01 05:59[:50]")
+pub fn parse_datetime(s: &str) -> Result<DateTime<Local>, String> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+        return Ok(datetime.with_timezone(&Local));
+    }
+
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+    .and_then(|datetime| Local.from_local_datetime(&datetime).earliest())
+    .ok_or(format!("invalid datetime: {}", s))
+}
@@ -1,10 +1,31 @@
 use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
-use std::{env, fs, ops::Range, path::PathBuf};
-use strum::IntoEnumIterator;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    ffi::OsString,
+    fmt, fs,
+    ops::{Range, RangeInclusive},
+    path::PathBuf,
+    time::Duration,
+};
 
-use crate::args::{Args, Language};
+use crate::{
+    args::{Args, Language},
+    cache::CacheConfig,
+    countdown::ReminderConfig,
+    custom::{self, CustomLanguage},
+    history::HistoryConfig,
+    logs::LogConfig,
+    output::{Normalization, OutputStrategy},
+    paths, process,
+    sink::SinkConfig,
+    theme::ThemeConfig,
+    warnings::Warning,
+    web::NetworkConfig,
+};
 
 pub struct OptionalParameters {
     pub year: Option<u16>,
@@ -12,17 +33,176 @@ pub struct OptionalParameters {
     pub language: Option<Language>,
 }
 
-#[derive(Serialize, Deserialize)]
+// where the input of a day is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputLocation {
+    // inside the project directory
+    Project,
+    // next to the project directory (shared by all languages of a day)
+    #[default]
+    Parent,
+    // in the data directory, independent of the template path
+    Central,
+}
+
+// the runtime executing javascript projects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JavaScriptRuntime {
+    #[default]
+    Node,
+    Deno,
+    Bun,
+}
+
+// how compiled languages are built, debug builds keep assertions and compile faster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    Debug,
+    #[default]
+    Release,
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_possible_value().unwrap().get_name())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
     template_path: String,
+    // base directories per year range (e.g. "2015-2020: /archive/aoc"), used for "{{root}}" in the template path
+    #[serde(default)]
+    roots: BTreeMap<String, String>,
     pub cookie: Option<String>,
+    // refuse to run modes which download or submit anything without a cookie, instead of only
+    // warning that inputs aren't downloaded and answers aren't submitted
+    #[serde(default)]
+    pub require_cookie: bool,
+    #[serde(default)]
+    pub input_location: InputLocation,
+    // pass the path of the input as the first argument to solutions (can be overridden per project)
+    #[serde(default)]
+    pub input_arg: bool,
+    // pipe the input into the stdin of solutions, so they can read it without opening the file
+    // (can be overridden per project, without it their stdin is empty)
+    #[serde(default = "default_input_stdin")]
+    pub input_stdin: bool,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    // environment variables per language injected into build and run commands
+    #[serde(default)]
+    pub env: HashMap<String, HashMap<String, String>>,
+    // how answers are extracted from the output of solutions (can be overridden per project)
+    #[serde(default)]
+    pub output: OutputStrategy,
+    // regex patterns extracting the answers (one per part, e.g. "Part 1: (.+)"), replace the output strategy
+    #[serde(default)]
+    pub answer_patterns: Vec<String>,
+    // a regex per language (or "default" for all of them) extracting both answers with "part1" and
+    // "part2" capture groups, e.g. "Part 1: (?P<part1>\d+)|Part 2: (?P<part2>\d+)"
+    #[serde(default)]
+    answer_regex: HashMap<String, String>,
+    // cleanup of the answers before they are submitted (trimming and line endings by default)
+    #[serde(default)]
+    pub normalize: Normalization,
+    // compiler used to build c++ projects
+    #[serde(default = "default_cpp_compiler")]
+    pub cpp_compiler: String,
+    // runtime used to run javascript projects
+    #[serde(default)]
+    pub javascript_runtime: JavaScriptRuntime,
+    // the profile compiled languages are built with (can be overridden with "--profile")
+    #[serde(default)]
+    pub profile: Profile,
+    // create a Project.toml in new julia projects (to manage their dependencies with Pkg)
+    #[serde(default)]
+    pub julia_project: bool,
+    // path to a shared helper library per language which new projects depend on
+    #[serde(default)]
+    common: HashMap<String, String>,
+    // directory per language whose files are copied into new projects (see retemplate mode), with
+    // "{{year}}", "{{day}}", "{{pad day}}", "{{language}}", "{{title}}", "{{unlock_date}}" and
+    // "{{stars_earned}}" replaced in them
+    #[serde(default)]
+    scaffold: HashMap<String, String>,
+    // build new projects in the background right after init mode created them, so the
+    // dependencies are compiled by the time the first real run happens
+    #[serde(default)]
+    pub prebuild: bool,
+    // opt-in streak reminders sent by the countdown mode
+    pub reminder: Option<ReminderConfig>,
+    // concurrency and rate limits for all requests
+    #[serde(default)]
+    pub network: NetworkConfig,
+    // where submissions and their verdicts are recorded
+    #[serde(default)]
+    pub history: HistoryConfig,
+    // show the submission cooldown left for a day before running its solution
+    #[serde(default = "default_show_cooldown")]
+    pub show_cooldown: bool,
+    // kill solutions running longer than this, e.g. "60s" (see --timeout)
+    #[serde(default, deserialize_with = "deserialize_timeout")]
+    pub timeout: Option<Duration>,
+    // ask before submitting each answer when running on a terminal (see --yes)
+    #[serde(default = "default_confirm_submit")]
+    pub confirm_submit: bool,
+    // warnings which aren't shown (by their id, e.g. "debug-build")
+    #[serde(default)]
+    pub allow: Vec<Warning>,
+    // colors and symbols of correct, incorrect and skipped results and timings
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    // opt-in logs of the full output of every run of a solution
+    pub logs: Option<LogConfig>,
+    // destinations the results of runs and comparisons are written to as json
+    #[serde(default)]
+    pub sinks: SinkConfig,
+    // languages with user defined commands, selectable like the built-in ones
+    #[serde(default)]
+    languages: BTreeMap<String, CustomLanguage>,
     #[serde(skip)]
     pub project_path: PathBuf,
+    // the puzzle of the project path (used by the commands of custom languages)
+    #[serde(skip)]
+    pub year: Option<u16>,
+    #[serde(skip)]
+    pub day: Option<u8>,
+    #[serde(skip)]
+    pub language: Option<Language>,
+}
+
+fn deserialize_timeout<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|timeout| process::parse_timeout(&timeout).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+fn default_cpp_compiler() -> String {
+    "g++".to_string()
+}
+
+fn default_input_stdin() -> bool {
+    true
+}
+
+fn default_show_cooldown() -> bool {
+    true
+}
+
+fn default_confirm_submit() -> bool {
+    true
 }
 
 impl Config {
     // helper function
-    fn build_param_regex(param: &str, paddable: bool) -> Regex {
+    pub fn build_param_regex(param: &str, paddable: bool) -> Regex {
         Regex::new(&format!(
             r"\{{\{{\s*{}{}\s*\}}\}}",
             if paddable { r"(pad\s+)?" } else { "" },
@@ -31,20 +211,75 @@ impl Config {
         .unwrap()
     }
 
+    // parses a year range key of the roots map ("2015-2020", "2021-", "-2018" or "2019")
+    fn parse_year_range(key: &str) -> Result<RangeInclusive<u16>> {
+        let parse = |value: &str, default: u16| -> Result<u16> {
+            match value.trim() {
+                "" => Ok(default),
+                value => value
+                    .parse()
+                    .map_err(|_| anyhow!("invalid year in roots: {}", key)),
+            }
+        };
+
+        match key.split_once('-') {
+            Some((start, end)) => Ok(parse(start, u16::MIN)?..=parse(end, u16::MAX)?),
+            None => {
+                let year = parse(key, 0)?;
+                Ok(year..=year)
+            }
+        }
+    }
+
+    // the base directory configured for the given year
+    fn root_for(&self, year: u16) -> Result<String> {
+        for (key, root) in &self.roots {
+            if Config::parse_year_range(key)?.contains(&year) {
+                return Ok(root.clone());
+            }
+        }
+
+        Err(anyhow!("no root configured for year {}", year))
+    }
+
+    // the cookie, or an error telling how to store one
+    pub fn cookie_for(&self, purpose: &str) -> Result<&str> {
+        self.cookie.as_deref().ok_or(anyhow!(
+            "a cookie is required to {} (store yours with 'aoc login')",
+            purpose
+        ))
+    }
+
     pub fn load() -> Result<(Self, OptionalParameters)> {
-        let home = dirs::home_dir().context("could not determine home directory")?;
+        let home = paths::home()?;
 
-        let config_path = home.join(".config").join("aoc").join("config.yaml");
+        let config_path = paths::config_file()?;
         let config_content = fs::read_to_string(&config_path)
             .with_context(|| format!("failed to read config file '{}'", config_path.display()))?;
 
         let mut config: Config = serde_yml::from_str(&config_content)
             .with_context(|| format!("failed to parse config file '{}'", config_path.display()))?;
 
+        // the custom languages have to be known before the language is extracted from the path
+        custom::register(&config.languages)?;
+
         if let Some(stripped) = config.template_path.strip_prefix("~/") {
             config.template_path = home.join(stripped).to_string_lossy().to_string();
         }
 
+        for path in config
+            .roots
+            .values_mut()
+            .chain(config.common.values_mut())
+            .chain(config.scaffold.values_mut())
+            .chain(config.history.path.iter_mut())
+            .chain(config.sinks.jsonl.iter_mut())
+        {
+            if let Some(stripped) = path.strip_prefix("~/") {
+                *path = home.join(stripped).to_string_lossy().to_string();
+            }
+        }
+
         let mut optional_params = OptionalParameters {
             year: None,
             day: None,
@@ -59,16 +294,20 @@ impl Config {
             // escaping the template path for later regex use
             {
                 // get the location ranges of regex patterns within the template path
-                let mut regex_patterns_locations: Vec<Range<usize>> =
-                    [("year", false), ("day", true), ("language", false)]
-                        .into_iter()
-                        .flat_map(|(name, paddable)| {
-                            let re = Config::build_param_regex(name, paddable);
-                            re.find_iter(&config.template_path)
-                                .map(|m| m.start()..m.end())
-                                .collect::<Vec<Range<usize>>>()
-                        })
-                        .collect();
+                let mut regex_patterns_locations: Vec<Range<usize>> = [
+                    ("root", false),
+                    ("year", false),
+                    ("day", true),
+                    ("language", false),
+                ]
+                .into_iter()
+                .flat_map(|(name, paddable)| {
+                    let re = Config::build_param_regex(name, paddable);
+                    re.find_iter(&config.template_path)
+                        .map(|m| m.start()..m.end())
+                        .collect::<Vec<Range<usize>>>()
+                })
+                .collect();
 
                 regex_patterns_locations.sort_by_key(|range| range.start);
 
@@ -100,16 +339,34 @@ impl Config {
                     ("{{pad day}}", r"(?P<padday>0[1-9]|1[0-9]|2[0-5])"),
                     (
                         "{{language}}",
-                        &format!(
-                            "(?P<language>{})",
-                            Language::iter()
-                                .map(|l| l.to_string())
-                                .collect::<Vec<String>>()
-                                .join("|")
-                        ),
+                        &format!("(?P<language>{})", {
+                            // the alternation takes the first name that matches, so a name which
+                            // starts with another one (e.g. "javascript" and "java") goes first
+                            let mut names: Vec<String> = Language::all()
+                                .iter()
+                                .map(|l| regex::escape(&l.to_string()))
+                                .collect();
+                            names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+                            names.join("|")
+                        }),
                     ),
                 ];
 
+                // the root is not extracted, it only has to match one of the configured roots
+                let mut roots: Vec<&String> = config.roots.values().collect();
+                roots.sort_by_key(|root| std::cmp::Reverse(root.len())); // prefer the longest root
+                pattern = pattern.replace(
+                    "{{root}}",
+                    &format!(
+                        "(?:{})",
+                        roots
+                            .into_iter()
+                            .map(|root| regex::escape(root.trim_end_matches('/')))
+                            .collect::<Vec<String>>()
+                            .join("|")
+                    ),
+                );
+
                 let mut end_pattern = String::new();
 
                 // insert optional group after each pattern that lasts until the end of the string
@@ -136,40 +393,137 @@ impl Config {
             // capture the parameters from the current working directory
             // and store them in the optional_params (later being used to override default arguments)
             if let Some(captures) =
-                Regex::new(&pattern)?.captures(&env::current_dir()?.to_string_lossy().into_owned())
+                Regex::new(&pattern)?.captures(&env::current_dir()?.to_string_lossy())
             {
-                optional_params.year = captures
-                    .name("year")
-                    .map(|m| m.as_str().parse().ok())
-                    .flatten();
+                optional_params.year = captures.name("year").and_then(|m| m.as_str().parse().ok());
 
                 optional_params.day = captures
                     .name("day")
                     .or(captures.name("padday"))
-                    .map(|m| m.as_str().parse().ok())
-                    .flatten();
+                    .and_then(|m| m.as_str().parse().ok());
 
                 optional_params.language = captures
                     .name("language")
-                    .map(|m| m.as_str().parse().ok())
-                    .flatten();
+                    .and_then(|m| m.as_str().parse().ok());
             }
         }
 
         Ok((config, optional_params))
     }
 
+    // the configured environment variables for the given language
+    pub fn language_env(&self, language: &Language) -> HashMap<String, String> {
+        self.env
+            .get(&language.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // the puzzle of a command on the project (its year, day, input and project as absolute paths),
+    // so solutions and build scripts can locate the input without hard-coding its path
+    pub fn puzzle_env(&self, year: u16, day: u8) -> Result<Vec<(&'static str, OsString)>> {
+        Ok(vec![
+            ("AOC_YEAR", year.to_string().into()),
+            ("AOC_DAY", day.to_string().into()),
+            (
+                "AOC_INPUT",
+                std::path::absolute(self.input_file(year, day)?)?.into(),
+            ),
+            (
+                "AOC_PROJECT",
+                std::path::absolute(&self.project_path)?.into(),
+            ),
+        ])
+    }
+
+    // the input file of the given day according to the configured input location
+    pub fn input_file(&self, year: u16, day: u8) -> Result<PathBuf> {
+        match self.input_location {
+            InputLocation::Project => Ok(self.project_path.join("input.txt")),
+            InputLocation::Parent => self
+                .project_path
+                .parent()
+                .map(|parent| parent.join("input.txt"))
+                .ok_or(anyhow!(
+                    "project path '{}' does not have a parent directory to store the input in, \
+                     set 'input_location' to 'project' or 'central' in the config",
+                    self.project_path.display()
+                )),
+            InputLocation::Central => Ok(paths::data_dir()?
+                .join("inputs")
+                .join(year.to_string())
+                .join(format!("{:0>2}.txt", day))),
+        }
+    }
+
+    // a copy of the config pointing to another project directory
+    pub fn with_project_path(&self, project_path: PathBuf) -> Self {
+        Self {
+            project_path,
+            ..self.clone()
+        }
+    }
+
+    // the configured answer patterns, the answer regex of the language is used if there are none
+    pub fn answer_patterns(&self) -> Vec<String> {
+        if !self.answer_patterns.is_empty() {
+            return self.answer_patterns.clone();
+        }
+
+        self.language
+            .and_then(|language| self.answer_regex.get(&language.to_string()))
+            .or_else(|| self.answer_regex.get("default"))
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    // the common module configured for the given language
+    pub fn common_path(&self, language: &Language) -> Option<PathBuf> {
+        self.common.get(&language.to_string()).map(PathBuf::from)
+    }
+
+    // the scaffold template configured for the given language
+    pub fn scaffold_path(&self, language: &Language) -> Option<PathBuf> {
+        self.scaffold.get(&language.to_string()).map(PathBuf::from)
+    }
+
+    // whether the template path contains the given parameter (e.g. "language")
+    pub fn uses_parameter(&self, name: &str) -> bool {
+        Config::build_param_regex(name, name == "day").is_match(&self.template_path)
+    }
+
     pub fn build(&mut self, args: &Args) -> Result<()> {
+        self.project_path = self.resolve(args.year, args.day, args.language)?;
+        self.year = args.year;
+        self.day = args.day;
+        self.language = args.language;
+
+        Ok(())
+    }
+
+    // the project path of the given parameters (keeping the placeholders of missing ones)
+    pub fn resolve(
+        &self,
+        year: Option<u16>,
+        day: Option<u8>,
+        language: Option<Language>,
+    ) -> Result<PathBuf> {
         let mut path = self.template_path.clone();
 
+        // only resolve the root if the template actually uses it
+        let root = match year {
+            Some(year) if self.uses_parameter("root") => {
+                Some(self.root_for(year)?.trim_end_matches('/').to_string())
+            }
+            _ => None,
+        };
+
         for (name, value, paddable) in [
-            ("year", args.year.map(|y| y.to_string()), false),
-            ("day", args.day.map(|d| d.to_string()), true),
-            (
-                "language",
-                args.language.map(|lang| lang.to_string()),
-                false,
-            ),
+            ("root", root, false),
+            ("year", year.map(|y| y.to_string()), false),
+            ("day", day.map(|d| d.to_string()), true),
+            ("language", language.map(|lang| lang.to_string()), false),
         ]
         .into_iter()
         {
@@ -194,8 +548,6 @@ impl Config {
             }
         }
 
-        self.project_path = PathBuf::from(path);
-
-        Ok(())
+        Ok(PathBuf::from(path))
     }
 }
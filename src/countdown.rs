@@ -0,0 +1,199 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Write},
+    process::Command,
+    time::Duration,
+};
+
+use crate::{
+    art, clock, command,
+    config::Config,
+    dates::{self, aoc_timezone},
+    theme, urls, web,
+};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReminderConfig {
+    // local time ("HH:MM") after which a reminder is sent if today's stars haven't been earned yet
+    pub at: String,
+    // send a desktop notification
+    #[serde(default = "default_desktop")]
+    pub desktop: bool,
+    // url receiving a json post with the reminder message
+    pub webhook: Option<String>,
+}
+
+fn default_desktop() -> bool {
+    true
+}
+
+// the next puzzle unlock after the given time as (year, day, unlock time)
+pub fn next_unlock(now: DateTime<Utc>) -> (u16, u8, DateTime<Utc>) {
+    let aoc_now = now.with_timezone(&aoc_timezone());
+
+    let last_day = dates::last_day(aoc_now.year() as u16) as u32;
+
+    let (year, day) = match (aoc_now.month(), aoc_now.day()) {
+        (12, day) if day < last_day => (aoc_now.year(), day + 1),
+        (12, _) => (aoc_now.year() + 1, 1),
+        _ => (aoc_now.year(), 1),
+    };
+
+    let unlock = dates::unlock(year as u16, day as u8).with_timezone(&Utc);
+
+    (year as u16, day as u8, unlock)
+}
+
+pub fn format_remaining(remaining: chrono::Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    let clock = format!(
+        "{:0>2}:{:0>2}:{:0>2}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    match days {
+        0 => clock,
+        days => format!("{}d {}", days, clock),
+    }
+}
+
+// counts down the given time on a single line of stderr (e.g. a submission cooldown)
+pub async fn wait(message: &str, duration: chrono::Duration) -> Result<()> {
+    let until = clock::now() + duration;
+
+    loop {
+        let remaining = until - clock::now();
+        if remaining <= chrono::Duration::zero() {
+            break;
+        }
+
+        eprint!("\r{} {}   ", message, format_remaining(remaining).bold());
+        io::stderr().flush()?;
+        tokio::time::sleep(Duration::from_secs(1).min(remaining.to_std()?)).await;
+    }
+
+    eprintln!();
+    Ok(())
+}
+
+async fn notify(reminder: &ReminderConfig, message: &str) -> Result<()> {
+    if reminder.desktop {
+        let mut notification = if cfg!(target_os = "macos") {
+            command!(
+                "osascript",
+                "-e",
+                format!(
+                    "display notification \"{}\" with title \"Advent of Code\"",
+                    message
+                )
+            )
+        } else {
+            command!("notify-send", "Advent of Code", message)
+        };
+        notification.output()?;
+    }
+
+    if let Some(webhook) = &reminder.webhook {
+        web::throttled(
+            reqwest::Client::new()
+                .post(webhook)
+                .json(&serde_json::json!({ "text": message }))
+                .send(),
+        )
+        .await?
+        .error_for_status()?;
+    }
+
+    Ok(())
+}
+
+// sends the reminder if today's puzzle is unlocked, it's past the reminder time and the stars are missing
+async fn check_reminder(config: &Config, reminder: &ReminderConfig, at: NaiveTime) -> Result<bool> {
+    let cookie = config.cookie_for("reminders")?;
+    let aoc_now = clock::now().with_timezone(&aoc_timezone());
+
+    if aoc_now.month() != 12 || aoc_now.day() > 25 || clock::now().time() < at {
+        return Ok(false);
+    }
+
+    let (year, day) = (aoc_now.year() as u16, aoc_now.day() as u8);
+    let page = art::fetch_calendar(config, cookie, year).await?;
+    let stars = art::parse_day_stars(&page).get(&day).copied().unwrap_or(0);
+
+    if stars < 2 {
+        notify(
+            reminder,
+            &format!(
+                "you have {}/2 stars for {}/{:0>2}, don't break your streak!",
+                stars, year, day
+            ),
+        )
+        .await?;
+    }
+
+    Ok(true)
+}
+
+// counts down to the next puzzle unlock (forever) and sends opt-in streak reminders
+pub async fn run(config: &Config) -> Result<()> {
+    let reminder_at = config
+        .reminder
+        .as_ref()
+        .map(|reminder| {
+            NaiveTime::parse_from_str(&reminder.at, "%H:%M")
+                .map_err(|_| anyhow!("invalid reminder time: {}", reminder.at))
+        })
+        .transpose()?;
+
+    let mut reminded_on: Option<NaiveDate> = None;
+    let mut target = next_unlock(clock::now().with_timezone(&Utc));
+
+    loop {
+        let now = clock::now().with_timezone(&Utc);
+        let next = next_unlock(now);
+
+        if next != target {
+            let (year, day, _) = target;
+            println!(
+                "\r{} {}",
+                theme::correct(&format!("{}/{:0>2} unlocked!", year, day)).bold(),
+                urls::puzzle(year, day)
+            );
+            target = next;
+        }
+
+        let (year, day, unlock) = target;
+        print!(
+            "\r{}/{:0>2} unlocks in {}   ",
+            year,
+            day,
+            format_remaining(unlock - now).bold()
+        );
+        io::stdout().flush()?;
+
+        // only check once per day (the calendar is cached, but a failed check shouldn't be retried every second)
+        if let (Some(reminder), Some(at)) = (&config.reminder, reminder_at)
+            && reminded_on != Some(clock::now().date_naive())
+        {
+            match check_reminder(config, reminder, at).await {
+                Ok(true) => reminded_on = Some(clock::now().date_naive()),
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!(
+                        "\n{}",
+                        theme::incorrect(&format!("failed to send reminder: {}", e))
+                    );
+                    reminded_on = Some(clock::now().date_naive());
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
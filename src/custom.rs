@@ -0,0 +1,124 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, process::Command, sync::OnceLock};
+
+use crate::{args::Language, command, config::Config};
+
+// a language defined in the config, e.g.
+//
+// languages:
+//   zig:
+//     detect: build.zig
+//     init: zig init
+//     build: zig build -Doptimize=ReleaseFast
+//     run: ./zig-out/bin/{{pad day}}
+//
+// the commands run in a shell inside of the project directory, "{{project_path}}", "{{year}}",
+// "{{day}}", "{{pad day}}", "{{common}}" and "{{profile}}" ("debug" or "release") are replaced
+// beforehand
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CustomLanguage {
+    // file marking a project of the language (any existing directory counts without one)
+    pub detect: Option<String>,
+    // command creating a new project (the directory is created beforehand)
+    pub init: Option<String>,
+    pub build: Option<String>,
+    pub run: String,
+    pub test: Option<String>,
+    // command downloading the dependencies of the project (see vendor mode)
+    pub vendor: Option<String>,
+    // path of the file produced by the build command, relative to the project (see path mode)
+    pub artifact: Option<String>,
+}
+
+static LANGUAGES: OnceLock<BTreeMap<String, CustomLanguage>> = OnceLock::new();
+
+// makes the languages of the config known for the rest of the process
+pub fn register(languages: &BTreeMap<String, CustomLanguage>) -> Result<()> {
+    for name in languages.keys() {
+        if Language::BUILTIN
+            .iter()
+            .any(|language| language.to_string() == name.to_lowercase())
+        {
+            return Err(anyhow!("custom language '{}' shadows a built-in one", name));
+        }
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow!("invalid custom language name: '{}'", name));
+        }
+    }
+
+    let _ = LANGUAGES.set(
+        languages
+            .iter()
+            .map(|(name, language)| (name.to_lowercase(), language.clone()))
+            .collect(),
+    );
+    Ok(())
+}
+
+// the names of the registered languages
+pub fn names() -> impl Iterator<Item = &'static str> {
+    LANGUAGES
+        .get()
+        .into_iter()
+        .flat_map(|languages| languages.keys().map(String::as_str))
+}
+
+pub fn get(name: &str) -> &'static CustomLanguage {
+    &LANGUAGES
+        .get()
+        .expect("custom languages are not registered")[name]
+}
+
+// the template with its placeholders replaced
+pub fn expand(template: &str, config: &Config, language: &Language) -> String {
+    let placeholder = |name: &str| Config::build_param_regex(name, name == "day");
+    let common = config
+        .common_path(language)
+        .map(|common| common.display().to_string())
+        .unwrap_or_default();
+
+    let mut expanded = template.replace("{{project_path}}", &config.project_path.to_string_lossy());
+    expanded = placeholder("common")
+        .replace_all(&expanded, common.as_str())
+        .to_string();
+    expanded = placeholder("profile")
+        .replace_all(&expanded, config.profile.to_string())
+        .to_string();
+    if let Some(year) = config.year {
+        expanded = placeholder("year")
+            .replace_all(&expanded, year.to_string())
+            .to_string();
+    }
+    if let Some(day) = config.day {
+        // "{{pad day}}" is padded to two digits, "{{day}}" isn't
+        expanded = placeholder("day")
+            .replace_all(&expanded, |captures: &regex::Captures| {
+                match captures.get(1) {
+                    Some(_) => format!("{:0>2}", day),
+                    None => day.to_string(),
+                }
+            })
+            .to_string();
+    }
+
+    expanded
+}
+
+// the command template with its placeholders replaced, run by the shell inside of the project
+pub fn command(template: &str, config: &Config, language: &Language) -> Command {
+    let expanded = expand(template, config, language);
+
+    // arguments added to the command (e.g. the input path) are forwarded to the template
+    let mut command = if cfg!(windows) {
+        command!("cmd", "/C", expanded)
+    } else {
+        command!("sh", "-c", format!("{} \"$@\"", expanded), "sh")
+    };
+    command.current_dir(&config.project_path);
+    command
+}
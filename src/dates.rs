@@ -0,0 +1,38 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone};
+
+// puzzles unlock at midnight in the timezone of adventofcode.com (EST, december has no daylight saving time)
+pub fn aoc_timezone() -> FixedOffset {
+    FixedOffset::west_opt(5 * 60 * 60).unwrap()
+}
+
+// the most recent year with puzzles at the given time in the given timezone
+// (the current year, if month is december, else previous year)
+pub fn latest_year<Tz: TimeZone>(now: DateTime<Tz>, timezone: &FixedOffset) -> u16 {
+    let now = now.with_timezone(timezone);
+    now.year() as u16 - (now.month() < 12) as u16
+}
+
+// the number of puzzles of the year (the event was shortened to 12 days starting with 2025)
+pub fn last_day(year: u16) -> u8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+// the year and day used when none are given at the given time in the given timezone:
+// the most recently unlocked puzzle in december (the last one once all are unlocked),
+// else day 1 of the latest year
+pub fn default_puzzle<Tz: TimeZone>(now: DateTime<Tz>, timezone: &FixedOffset) -> (u16, u8) {
+    let now = now.with_timezone(timezone);
+    let day = match now.month() {
+        12 => (now.day() as u8).min(last_day(now.year() as u16)),
+        _ => 1,
+    };
+
+    (latest_year(now, timezone), day)
+}
+
+// the time the puzzle of the given day unlocks
+pub fn unlock(year: u16, day: u8) -> DateTime<FixedOffset> {
+    aoc_timezone()
+        .with_ymd_and_hms(year as i32, 12, day as u32, 0, 0, 0)
+        .unwrap()
+}
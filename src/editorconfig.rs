@@ -0,0 +1,272 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndentStyle {
+    Space,
+    Tab,
+}
+
+// the properties of a file which affect generated content ("unset" is the same as missing)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Properties {
+    indent_style: Option<IndentStyle>,
+    indent_size: Option<usize>,
+    end_of_line: Option<&'static str>,
+    insert_final_newline: Option<bool>,
+    trim_trailing_whitespace: Option<bool>,
+}
+
+impl Properties {
+    fn set(&mut self, key: &str, value: &str) {
+        let value = value.to_lowercase();
+        let flag = match value.as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        };
+
+        match key {
+            "indent_style" => {
+                self.indent_style = match value.as_str() {
+                    "space" => Some(IndentStyle::Space),
+                    "tab" => Some(IndentStyle::Tab),
+                    _ => None,
+                }
+            }
+            "indent_size" => self.indent_size = value.parse().ok(),
+            "end_of_line" => {
+                self.end_of_line = match value.as_str() {
+                    "lf" => Some("\n"),
+                    "crlf" => Some("\r\n"),
+                    "cr" => Some("\r"),
+                    _ => None,
+                }
+            }
+            "insert_final_newline" => self.insert_final_newline = flag,
+            "trim_trailing_whitespace" => self.trim_trailing_whitespace = flag,
+            _ => {}
+        }
+    }
+}
+
+struct Section {
+    glob: Regex,
+    properties: Vec<(String, String)>,
+}
+
+// a parsed ".editorconfig" file
+struct File {
+    dir: PathBuf,
+    root: bool,
+    sections: Vec<Section>,
+}
+
+// translates a section name into a regex matching paths relative to the .editorconfig file
+// ("*", "**", "?", "[...]" and "{a,b}" are supported, names without a "/" match in any directory)
+fn glob_regex(glob: &str) -> Option<Regex> {
+    let mut pattern = String::new();
+    let mut chars = glob.trim_start_matches('/').chars().peekable();
+    let mut braces = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                pattern.push_str(".*");
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            '[' if chars.peek() == Some(&'!') => {
+                chars.next();
+                pattern.push_str("[^");
+            }
+            '[' => pattern.push('['),
+            ']' => pattern.push(']'),
+            '{' => {
+                braces += 1;
+                pattern.push_str("(?:");
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+                pattern.push(')');
+            }
+            ',' if braces > 0 => pattern.push('|'),
+            '\\' => pattern.push_str(&regex::escape(&chars.next()?.to_string())),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    let prefix = if glob.contains('/') { "" } else { "(?:.*/)?" };
+    Regex::new(&format!("^{}{}$", prefix, pattern)).ok()
+}
+
+impl File {
+    fn parse(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read '{}'", path.display()))?;
+        let mut file = File {
+            dir: path.parent().unwrap().to_path_buf(),
+            root: false,
+            sections: Vec::new(),
+        };
+        // properties of sections with unsupported names are ignored
+        let mut current: Option<Section> = None;
+        let mut ignored = false;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                file.sections.extend(current.take());
+                current = glob_regex(name).map(|glob| Section {
+                    glob,
+                    properties: Vec::new(),
+                });
+                ignored = current.is_none();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim().to_lowercase(), value.trim().to_string());
+
+            match &mut current {
+                Some(section) => section.properties.push((key, value)),
+                None if !ignored && key == "root" => file.root = value.eq_ignore_ascii_case("true"),
+                None => {}
+            }
+        }
+        file.sections.extend(current);
+
+        Ok(file)
+    }
+}
+
+// the .editorconfig files applying to a directory, generated files follow their indentation,
+// line endings and final newlines so they don't show up as changed after formatting
+pub struct EditorConfig {
+    // the closest file last, so its properties take precedence
+    files: Vec<File>,
+}
+
+impl EditorConfig {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+
+        for dir in dir.ancestors() {
+            let path = dir.join(".editorconfig");
+            if !path.is_file() {
+                continue;
+            }
+
+            let file = File::parse(&path)?;
+            let root = file.root;
+            files.push(file);
+            if root {
+                break;
+            }
+        }
+
+        files.reverse();
+        Ok(Self { files })
+    }
+
+    fn properties(&self, path: &Path) -> Properties {
+        let mut properties = Properties::default();
+
+        for file in &self.files {
+            let Ok(relative) = path.strip_prefix(&file.dir) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+
+            for section in file.sections.iter().filter(|s| s.glob.is_match(&relative)) {
+                for (key, value) in &section.properties {
+                    properties.set(key, value);
+                }
+            }
+        }
+
+        properties
+    }
+
+    // the content of a generated file at the given path, reformatted according to the properties
+    // of the file (content which isn't text is left alone)
+    pub fn format(&self, path: &Path, content: &[u8]) -> Vec<u8> {
+        let properties = self.properties(path);
+        let Ok(text) = std::str::from_utf8(content) else {
+            return content.to_vec();
+        };
+        if properties == Properties::default() {
+            return content.to_vec();
+        }
+
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+        if properties.indent_style.is_some() || properties.indent_size.is_some() {
+            lines = reindent(&lines, &properties);
+        }
+        if properties.trim_trailing_whitespace == Some(true) {
+            for line in &mut lines {
+                line.truncate(line.trim_end().len());
+            }
+        }
+
+        let newline = properties
+            .end_of_line
+            .unwrap_or(match text.contains("\r\n") {
+                true => "\r\n",
+                false => "\n",
+            });
+        let final_newline = properties
+            .insert_final_newline
+            .unwrap_or(text.ends_with('\n'));
+
+        let mut formatted = lines.join(newline);
+        if final_newline && !lines.is_empty() {
+            formatted.push_str(newline);
+        }
+        formatted.into_bytes()
+    }
+}
+
+// replaces the indentation of the lines (the unit is the smallest indentation by spaces)
+fn reindent(lines: &[String], properties: &Properties) -> Vec<String> {
+    let indentation = |line: &str| line.len() - line.trim_start_matches(' ').len();
+    let Some(unit) = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line))
+        .filter(|&spaces| spaces > 0)
+        .min()
+    else {
+        return lines.to_vec();
+    };
+
+    let size = properties.indent_size.unwrap_or(unit);
+    let level = match properties.indent_style {
+        Some(IndentStyle::Tab) => "\t".to_string(),
+        _ => " ".repeat(size),
+    };
+
+    lines
+        .iter()
+        .map(|line| {
+            let spaces = indentation(line);
+            format!(
+                "{}{}{}",
+                level.repeat(spaces / unit),
+                " ".repeat(spaces % unit),
+                &line[spaces..]
+            )
+        })
+        .collect()
+}
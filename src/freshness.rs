@@ -0,0 +1,69 @@
+use anyhow::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::UNIX_EPOCH,
+};
+
+use crate::{args::Language, config::Config, metadata::METADATA_FILE, storage};
+
+// the sources of the project and its common module (every file by its path, size and modification
+// time) along with the build command, a build is only needed once this changes
+pub fn fingerprint(config: &Config, language: &Language, build: &Command) -> Result<String> {
+    let mut entries = vec![format!("{:?}", build)];
+
+    collect(&config.project_path, Path::new(""), language, &mut entries)?;
+    if let Some(common) = config.common_path(language) {
+        match common.is_dir() {
+            true => collect(&common, Path::new(""), language, &mut entries)?,
+            false => entries.push(entry(&common, &common)?),
+        }
+    }
+
+    entries.sort();
+    Ok(storage::hash(entries.join("\n").as_bytes()))
+}
+
+fn entry(path: &Path, relative: &Path) -> Result<String> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    Ok(format!(
+        "{} {} {}",
+        relative.display(),
+        metadata.len(),
+        modified.as_nanos()
+    ))
+}
+
+fn collect(
+    root: &Path,
+    relative: &Path,
+    language: &Language,
+    entries: &mut Vec<String>,
+) -> Result<()> {
+    for dir_entry in fs::read_dir(root.join(relative))? {
+        let dir_entry = dir_entry?;
+        let relative: PathBuf = relative.join(dir_entry.file_name());
+
+        // hidden files (e.g. version control data), the metadata of the runner and whatever the
+        // build writes aren't sources
+        if dir_entry.file_name().to_string_lossy().starts_with('.')
+            || relative == Path::new(METADATA_FILE)
+            || language.is_build_output(&relative)
+        {
+            continue;
+        }
+
+        match dir_entry.file_type()?.is_dir() {
+            true => collect(root, &relative, language, entries)?,
+            false => entries.push(entry(&dir_entry.path(), &relative)?),
+        }
+    }
+
+    Ok(())
+}
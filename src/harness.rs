@@ -0,0 +1,416 @@
+use anyhow::{Result, anyhow};
+
+use crate::{
+    args::Language,
+    config::{Config, JavaScriptRuntime},
+};
+
+// a solution skeleton per language which reads the input from AOC_INPUT, times "part1" and "part2"
+// and prints the answers and timings as a single json object (see the json output strategy), the
+// parts return nothing until they are solved, so unsolved parts are never submitted
+pub fn files(
+    language: &Language,
+    config: &Config,
+) -> Result<&'static [(&'static str, &'static str)]> {
+    match *language {
+        Language::Rust => Ok(&[("src/main.rs", RUST)]),
+        Language::CSharp => Ok(&[("Program.cs", CSHARP)]),
+        Language::FSharp => Ok(&[("Program.fs", FSHARP)]),
+        Language::Java => Ok(&[("Main.java", JAVA)]),
+        Language::Python => Ok(&[("main.py", PYTHON)]),
+        Language::Cpp => Ok(&[("main.cpp", CPP)]),
+        Language::TypeScript => Ok(&[("main.ts", TYPESCRIPT)]),
+        // deno doesn't know "require" in plain javascript files
+        Language::JavaScript => match config.javascript_runtime {
+            JavaScriptRuntime::Deno => Ok(&[("main.js", JAVASCRIPT_MODULE)]),
+            _ => Ok(&[("main.js", JAVASCRIPT)]),
+        },
+        Language::Ruby => Ok(&[("main.rb", RUBY)]),
+        Language::Julia => Ok(&[("main.jl", JULIA)]),
+        Language::Scala => Ok(&[("Main.scala", SCALA)]),
+        _ => Err(anyhow!("there is no timing harness for {}", language)),
+    }
+}
+
+const RUST: &str = r##"use std::{env, fs, time::Instant};
+
+fn part1(_input: &str) -> Option<String> {
+    None
+}
+
+fn part2(_input: &str) -> Option<String> {
+    None
+}
+
+// prints the answers and timings in the json protocol of the runner
+fn main() {
+    let input = fs::read_to_string(env::var("AOC_INPUT").expect("AOC_INPUT is not set")).unwrap();
+    let parts: [(&str, fn(&str) -> Option<String>); 2] = [("part1", part1), ("part2", part2)];
+
+    let mut fields = Vec::new();
+    let mut total = 0.0;
+    for (name, solve) in parts {
+        let start = Instant::now();
+        let answer = solve(&input);
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        total += ms;
+
+        if let Some(answer) = answer {
+            fields.push(format!("\"{}\": {:?}", name, answer));
+        }
+        fields.push(format!("\"{}_ms\": {}", name, ms));
+    }
+    fields.push(format!("\"time_ms\": {}", total));
+
+    println!("{{{}}}", fields.join(", "));
+}
+"##;
+
+const CSHARP: &str = r##"using System.Diagnostics;
+using System.Text.Json;
+
+string? Part1(string input) => null;
+
+string? Part2(string input) => null;
+
+// prints the answers and timings in the json protocol of the runner
+var input = File.ReadAllText(Environment.GetEnvironmentVariable("AOC_INPUT")!);
+var parts = new (string, Func<string, string?>)[] { ("part1", Part1), ("part2", Part2) };
+
+var result = new Dictionary<string, object>();
+var total = 0.0;
+foreach (var (name, solve) in parts)
+{
+    var stopwatch = Stopwatch.StartNew();
+    var answer = solve(input);
+    var ms = stopwatch.Elapsed.TotalMilliseconds;
+    total += ms;
+
+    if (answer != null)
+    {
+        result[name] = answer;
+    }
+    result[$"{name}_ms"] = ms;
+}
+result["time_ms"] = total;
+
+Console.WriteLine(JsonSerializer.Serialize(result));
+"##;
+
+const FSHARP: &str = r##"open System
+open System.Collections.Generic
+open System.Diagnostics
+open System.IO
+open System.Text.Json
+
+let part1 (input: string) : string option = None
+
+let part2 (input: string) : string option = None
+
+// prints the answers and timings in the json protocol of the runner
+let input = File.ReadAllText(Environment.GetEnvironmentVariable "AOC_INPUT")
+let result = Dictionary<string, obj>()
+let mutable total = 0.0
+
+for name, solve in [ "part1", part1; "part2", part2 ] do
+    let stopwatch = Stopwatch.StartNew()
+    let answer = solve input
+    let ms = stopwatch.Elapsed.TotalMilliseconds
+    total <- total + ms
+
+    answer |> Option.iter (fun answer -> result[name] <- box answer)
+    result[name + "_ms"] <- box ms
+
+result["time_ms"] <- box total
+
+printfn "%s" (JsonSerializer.Serialize result)
+"##;
+
+const JAVA: &str = r##"import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.function.Function;
+
+public class Main {
+    static String part1(String input) {
+        return null;
+    }
+
+    static String part2(String input) {
+        return null;
+    }
+
+    static String quote(String s) {
+        return "\"" + s.replace("\\", "\\\\").replace("\"", "\\\"") + "\"";
+    }
+
+    // prints the answers and timings in the json protocol of the runner
+    public static void main(String[] args) throws Exception {
+        String input = Files.readString(Path.of(System.getenv("AOC_INPUT")));
+        List<Function<String, String>> parts = List.of(Main::part1, Main::part2);
+
+        List<String> fields = new ArrayList<>();
+        double total = 0;
+        for (int i = 0; i < parts.size(); i++) {
+            String name = "part" + (i + 1);
+            long start = System.nanoTime();
+            String answer = parts.get(i).apply(input);
+            double ms = (System.nanoTime() - start) / 1e6;
+            total += ms;
+
+            if (answer != null) {
+                fields.add(quote(name) + ": " + quote(answer));
+            }
+            fields.add(quote(name + "_ms") + ": " + ms);
+        }
+        fields.add(quote("time_ms") + ": " + total);
+
+        System.out.println("{" + String.join(", ", fields) + "}");
+    }
+}
+"##;
+
+const PYTHON: &str = r##"import json
+import os
+import time
+
+
+def part1(input):
+    return None
+
+
+def part2(input):
+    return None
+
+
+# prints the answers and timings in the json protocol of the runner
+if __name__ == "__main__":
+    with open(os.environ["AOC_INPUT"]) as file:
+        input = file.read()
+
+    result = {"time_ms": 0.0}
+    for name, solve in [("part1", part1), ("part2", part2)]:
+        start = time.perf_counter()
+        answer = solve(input)
+        ms = (time.perf_counter() - start) * 1000
+        result["time_ms"] += ms
+
+        if answer is not None:
+            result[name] = str(answer)
+        result[f"{name}_ms"] = ms
+
+    print(json.dumps(result))
+"##;
+
+const CPP: &str = r##"#include <chrono>
+#include <cstdlib>
+#include <fstream>
+#include <iostream>
+#include <optional>
+#include <sstream>
+#include <string>
+
+std::optional<std::string> part1(const std::string &input) {
+    return std::nullopt;
+}
+
+std::optional<std::string> part2(const std::string &input) {
+    return std::nullopt;
+}
+
+std::string quote(const std::string &s) {
+    std::string quoted = "\"";
+    for (char c : s) {
+        if (c == '"' || c == '\\') {
+            quoted += '\\';
+        }
+        quoted += c;
+    }
+    return quoted + "\"";
+}
+
+// prints the answers and timings in the json protocol of the runner
+int main() {
+    std::ifstream file(std::getenv("AOC_INPUT"));
+    std::stringstream buffer;
+    buffer << file.rdbuf();
+    const std::string input = buffer.str();
+
+    std::optional<std::string> (*parts[])(const std::string &) = {part1, part2};
+
+    std::ostringstream json;
+    double total = 0;
+    json << "{";
+    for (int i = 0; i < 2; i++) {
+        const std::string name = "part" + std::to_string(i + 1);
+        const auto start = std::chrono::steady_clock::now();
+        const auto answer = parts[i](input);
+        const std::chrono::duration<double, std::milli> ms = std::chrono::steady_clock::now() - start;
+        total += ms.count();
+
+        if (answer) {
+            json << quote(name) << ": " << quote(*answer) << ", ";
+        }
+        json << quote(name + "_ms") << ": " << ms.count() << ", ";
+    }
+    json << quote("time_ms") << ": " << total << "}";
+
+    std::cout << json.str() << std::endl;
+}
+"##;
+
+const TYPESCRIPT: &str = r##"import * as fs from "fs";
+
+function part1(input: string): string | null {
+    return null;
+}
+
+function part2(input: string): string | null {
+    return null;
+}
+
+// prints the answers and timings in the json protocol of the runner
+const input = fs.readFileSync(process.env.AOC_INPUT!, "utf8");
+const parts: [string, (input: string) => string | null][] = [["part1", part1], ["part2", part2]];
+
+const result: Record<string, string | number> = {};
+let total = 0;
+for (const [name, solve] of parts) {
+    const start = performance.now();
+    const answer = solve(input);
+    const ms = performance.now() - start;
+    total += ms;
+
+    if (answer !== null) {
+        result[name] = answer;
+    }
+    result[`${name}_ms`] = ms;
+}
+result.time_ms = total;
+
+console.log(JSON.stringify(result));
+"##;
+
+// shared by both javascript harnesses (after the import of "fs")
+macro_rules! javascript {
+    ($import:literal) => {
+        concat!(
+            $import,
+            r##"
+
+function part1(input) {
+    return null;
+}
+
+function part2(input) {
+    return null;
+}
+
+// prints the answers and timings in the json protocol of the runner
+const input = fs.readFileSync(process.env.AOC_INPUT, "utf8");
+
+const result = {};
+let total = 0;
+for (const [name, solve] of [["part1", part1], ["part2", part2]]) {
+    const start = performance.now();
+    const answer = solve(input);
+    const ms = performance.now() - start;
+    total += ms;
+
+    if (answer !== null && answer !== undefined) {
+        result[name] = String(answer);
+    }
+    result[`${name}_ms`] = ms;
+}
+result.time_ms = total;
+
+console.log(JSON.stringify(result));
+"##
+        )
+    };
+}
+
+const JAVASCRIPT: &str = javascript!(r#"const fs = require("fs");"#);
+
+const JAVASCRIPT_MODULE: &str = javascript!(r#"import fs from "node:fs";"#);
+
+const RUBY: &str = r##"require "json"
+
+def part1(input)
+  nil
+end
+
+def part2(input)
+  nil
+end
+
+# prints the answers and timings in the json protocol of the runner
+input = File.read(ENV.fetch("AOC_INPUT"))
+
+result = { time_ms: 0.0 }
+{ part1: method(:part1), part2: method(:part2) }.each do |name, solve|
+  start = Process.clock_gettime(Process::CLOCK_MONOTONIC)
+  answer = solve.call(input)
+  ms = (Process.clock_gettime(Process::CLOCK_MONOTONIC) - start) * 1000
+  result[:time_ms] += ms
+
+  result[name] = answer.to_s unless answer.nil?
+  result[:"#{name}_ms"] = ms
+end
+
+puts result.to_json
+"##;
+
+const JULIA: &str = r##"part1(input) = nothing
+
+part2(input) = nothing
+
+quote_json(s) = "\"" * replace(s, "\\" => "\\\\", "\"" => "\\\"") * "\""
+
+# prints the answers and timings in the json protocol of the runner
+function main()
+    input = read(ENV["AOC_INPUT"], String)
+
+    fields = String[]
+    total = 0.0
+    for (name, solve) in [("part1", part1), ("part2", part2)]
+        start = time_ns()
+        answer = solve(input)
+        ms = (time_ns() - start) / 1e6
+        total += ms
+
+        answer === nothing || push!(fields, "$(quote_json(name)): $(quote_json(string(answer)))")
+        push!(fields, "$(quote_json(name * "_ms")): $ms")
+    end
+    push!(fields, "$(quote_json("time_ms")): $total")
+
+    println("{", join(fields, ", "), "}")
+end
+
+main()
+"##;
+
+const SCALA: &str = r##"def part1(input: String): Option[String] = None
+
+def part2(input: String): Option[String] = None
+
+def quote(s: String): String = "\"" + s.replace("\\", "\\\\").replace("\"", "\\\"") + "\""
+
+// prints the answers and timings in the json protocol of the runner
+@main def main(): Unit =
+  val input = scala.io.Source.fromFile(sys.env("AOC_INPUT")).mkString
+  val parts = List(("part1", part1), ("part2", part2))
+
+  var total = 0.0
+  val fields = parts.flatMap { (name, solve) =>
+    val start = System.nanoTime()
+    val answer = solve(input)
+    val ms = (System.nanoTime() - start) / 1e6
+    total += ms
+
+    answer.map(answer => quote(name) + ": " + quote(answer)).toList :+ (quote(name + "_ms") + ": " + ms)
+  }
+
+  println((fields :+ (quote("time_ms") + ": " + total)).mkString("{", ", ", "}"))
+"##;
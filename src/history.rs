@@ -0,0 +1,328 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, Local};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::{clock, paths, storage, submit::Verdict};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HistoryConfig {
+    // directory the submission history is stored in, can be shared (e.g. a git-synced folder
+    // when solving as a team on separate machines)
+    pub path: Option<String>,
+    // who submitted an answer, distinguishes the members of a team (defaults to the user name)
+    pub name: Option<String>,
+}
+
+impl HistoryConfig {
+    fn dir(&self) -> Result<PathBuf> {
+        match &self.path {
+            Some(path) => Ok(PathBuf::from(path)),
+            None => Ok(paths::data_dir()?.join("submissions")),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name
+            .clone()
+            .or_else(paths::user)
+            .unwrap_or_else(|| "me".to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SubmissionRecord {
+    pub part: u8,
+    pub by: String,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub submitted_at: String,
+    // until when the server won't accept another answer for this part (if it asked to wait)
+    pub cooldown_until: Option<String>,
+    // the answers judged before the last one (so the same wrong answer is never submitted twice)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub earlier: Vec<JudgedAnswer>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JudgedAnswer {
+    pub answer: String,
+    pub verdict: Verdict,
+    #[serde(default)]
+    pub submitted_at: String,
+}
+
+impl SubmissionRecord {
+    // the cooldown left at the given time, none if answers are accepted again
+    pub fn remaining_cooldown(&self, now: DateTime<Local>) -> Option<Duration> {
+        let until = DateTime::parse_from_rfc3339(self.cooldown_until.as_ref()?).ok()?;
+        Some(until.with_timezone(&Local) - now).filter(|remaining| *remaining > Duration::zero())
+    }
+}
+
+// the last submission (and its verdict) per part and team member of a single day
+//
+// every day is stored in its own file ("<year>/<day>.yaml") with the records sorted by part and
+// member, so members working on different days never touch the same file and concurrent edits
+// to the same day end up in separate hunks (which are merged on load if git left conflict markers)
+pub struct SubmissionHistory {
+    path: PathBuf,
+    name: String,
+    pub submissions: Vec<SubmissionRecord>,
+}
+
+// parses the wait times of the server ("one minute", "5 minutes", "4m 39s", ...)
+pub fn parse_wait(wait: &str) -> Option<Duration> {
+    let re = Regex::new(r"(?i)\b(?P<amount>\d+|an?|one)\s*(?P<unit>h|m|s)").unwrap();
+    let mut total = Duration::zero();
+    let mut matched = false;
+
+    for captures in re.captures_iter(wait) {
+        let amount = match &captures["amount"].to_lowercase()[..] {
+            "a" | "an" | "one" => 1,
+            amount => amount.parse().ok()?,
+        };
+
+        total += match &captures["unit"].to_lowercase()[..] {
+            "h" => Duration::hours(amount),
+            "m" => Duration::minutes(amount),
+            _ => Duration::seconds(amount),
+        };
+        matched = true;
+    }
+
+    matched.then_some(total)
+}
+
+// splits a file with git conflict markers into the two versions ("ours" and "theirs")
+fn split_conflict(content: &str) -> Option<(String, String)> {
+    if !content.lines().any(|line| line.starts_with("<<<<<<<")) {
+        return None;
+    }
+
+    let (mut ours, mut theirs) = (String::new(), String::new());
+    // 0 = outside of a conflict, 1 = ours, 2 = common ancestor (diff3 style), 3 = theirs
+    let mut side = 0;
+
+    for line in content.lines() {
+        side = match (side, line) {
+            (_, line) if line.starts_with("<<<<<<<") => 1,
+            (1, line) if line.starts_with("|||||||") => 2,
+            (1 | 2, line) if line.starts_with("=======") => 3,
+            (_, line) if line.starts_with(">>>>>>>") => 0,
+            (side, line) => {
+                if matches!(side, 0 | 1) {
+                    ours.push_str(line);
+                    ours.push('\n');
+                }
+                if matches!(side, 0 | 3) {
+                    theirs.push_str(line);
+                    theirs.push('\n');
+                }
+                side
+            }
+        };
+    }
+
+    Some((ours, theirs))
+}
+
+impl SubmissionHistory {
+    pub fn load(config: &HistoryConfig, year: u16, day: u8) -> Result<Self> {
+        let path = config
+            .dir()?
+            .join(year.to_string())
+            .join(format!("{:0>2}.yaml", day));
+        let mut history = Self {
+            path,
+            name: config.name(),
+            submissions: Vec::new(),
+        };
+
+        if !history.path.exists() {
+            return Ok(history);
+        }
+
+        let content = fs::read_to_string(&history.path).with_context(|| {
+            format!(
+                "failed to read submission history '{}'",
+                history.path.display()
+            )
+        })?;
+        let parse = |content: &str| -> Result<Vec<SubmissionRecord>> {
+            serde_yml::from_str::<Option<Vec<SubmissionRecord>>>(content)
+                .map(Option::unwrap_or_default)
+                .with_context(|| {
+                    format!(
+                        "failed to parse submission history '{}'",
+                        history.path.display()
+                    )
+                })
+        };
+
+        match split_conflict(&content) {
+            // keep the newer record of both versions (the merged file is written on the next save)
+            Some((ours, theirs)) => {
+                for record in parse(&ours)?.into_iter().chain(parse(&theirs)?) {
+                    history.insert(record);
+                }
+            }
+            None => history.submissions = parse(&content)?,
+        }
+
+        Ok(history)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut submissions = self.submissions.clone();
+        submissions.sort_by(|a, b| (a.part, &a.by).cmp(&(b.part, &b.by)));
+
+        // written as a whole, so sync tools never pick up a half written file
+        fs::create_dir_all(self.path.parent().unwrap())?;
+        storage::write(&self.path, serde_yml::to_string(&submissions)?).with_context(|| {
+            format!(
+                "failed to write submission history '{}'",
+                self.path.display()
+            )
+        })
+    }
+
+    // the last submission for the part by the current user
+    pub fn get(&self, part: u8) -> Option<&SubmissionRecord> {
+        self.submissions
+            .iter()
+            .find(|s| s.part == part && s.by == self.name)
+    }
+
+    // the verdict the answer already got for the part (from any earlier submission of the user)
+    pub fn verdict(&self, part: u8, answer: &str) -> Option<&Verdict> {
+        let record = self.get(part)?;
+
+        std::iter::once((&record.answer, &record.verdict))
+            .chain(
+                record
+                    .earlier
+                    .iter()
+                    .map(|judged| (&judged.answer, &judged.verdict)),
+            )
+            .find(|(judged, verdict)| *judged == answer && verdict.is_judged())
+            .map(|(_, verdict)| verdict)
+    }
+
+    // the bounds of the answer of the part according to the "too high" and "too low" hints the user
+    // got for the part so far, as (exclusive lower bound, exclusive upper bound)
+    pub fn bounds(&self, part: u8) -> (Option<i128>, Option<i128>) {
+        let Some(record) = self.get(part) else {
+            return (None, None);
+        };
+
+        let judged = std::iter::once((&record.answer, &record.verdict)).chain(
+            record
+                .earlier
+                .iter()
+                .map(|judged| (&judged.answer, &judged.verdict)),
+        );
+
+        let (mut lower, mut upper) = (None::<i128>, None::<i128>);
+        for (answer, verdict) in judged {
+            let Ok(answer) = answer.trim().parse::<i128>() else {
+                continue;
+            };
+            match verdict {
+                Verdict::TooLow { .. } => lower = Some(lower.map_or(answer, |l| l.max(answer))),
+                Verdict::TooHigh { .. } => upper = Some(upper.map_or(answer, |u| u.min(answer))),
+                _ => {}
+            }
+        }
+
+        (lower, upper)
+    }
+
+    // inserts a record unless a newer one for the same part and member exists
+    fn insert(&mut self, record: SubmissionRecord) {
+        let newer = |a: &SubmissionRecord, b: &SubmissionRecord| match (
+            DateTime::parse_from_rfc3339(&a.submitted_at),
+            DateTime::parse_from_rfc3339(&b.submitted_at),
+        ) {
+            (Ok(a), Ok(b)) => a > b,
+            _ => a.submitted_at > b.submitted_at,
+        };
+
+        match self
+            .submissions
+            .iter_mut()
+            .find(|s| s.part == record.part && s.by == record.by)
+        {
+            Some(existing) if newer(&record, existing) => *existing = record,
+            Some(_) => {}
+            None => self.submissions.push(record),
+        }
+    }
+
+    // records a part the current user solved before the history was kept (with an unknown answer),
+    // returns false if a correct answer was already recorded
+    pub fn backfill(&mut self, part: u8, solved_at: DateTime<FixedOffset>) -> bool {
+        if self
+            .get(part)
+            .is_some_and(|record| record.verdict.is_correct())
+        {
+            return false;
+        }
+
+        let name = self.name.clone();
+        self.submissions
+            .retain(|s| !(s.part == part && s.by == name));
+        self.submissions.push(SubmissionRecord {
+            part,
+            by: name,
+            answer: String::new(),
+            verdict: Verdict::Correct,
+            submitted_at: solved_at.to_rfc3339(),
+            cooldown_until: None,
+            earlier: Vec::new(),
+        });
+        true
+    }
+
+    // records a judged submission of the current user, replacing the previous one for the same part
+    pub fn record(&mut self, part: u8, answer: &str, verdict: &Verdict) {
+        let now = clock::now();
+        // answers which weren't judged (e.g. rate limited) don't need to be remembered
+        let earlier = match self.get(part) {
+            Some(previous) => {
+                let mut earlier = previous.earlier.clone();
+                if previous.verdict.is_judged() {
+                    earlier.push(JudgedAnswer {
+                        answer: previous.answer.clone(),
+                        verdict: previous.verdict.clone(),
+                        submitted_at: previous.submitted_at.clone(),
+                    });
+                }
+                earlier.retain(|judged| judged.answer != answer);
+                earlier
+            }
+            None => Vec::new(),
+        };
+        let wait = match verdict {
+            Verdict::Incorrect { wait } | Verdict::TooHigh { wait } | Verdict::TooLow { wait } => {
+                wait.as_deref()
+            }
+            Verdict::RateLimited { wait } => Some(wait.as_str()),
+            _ => None,
+        };
+
+        self.insert(SubmissionRecord {
+            part,
+            by: self.name.clone(),
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+            submitted_at: now.to_rfc3339(),
+            cooldown_until: wait
+                .and_then(parse_wait)
+                .map(|wait| (now + wait).to_rfc3339()),
+            earlier,
+        });
+    }
+}
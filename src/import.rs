@@ -0,0 +1,90 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, FixedOffset};
+use regex::Regex;
+
+use crate::{config::Config, dates, history::SubmissionHistory, urls, web};
+
+// the solve times per day of the personal times page ("/<year>/leaderboard/self"), e.g.
+//
+//       --------Part 1--------   --------Part 2--------
+// Day       Time   Rank  Score       Time   Rank  Score
+//   2   00:12:34   1234      0       >24h  23456      0
+//   1   00:05:01    321      0          -      -      -
+//
+// the times are relative to the unlock of the puzzle, parts solved more than a day later only
+// show up as ">24h" (these are recorded as solved 24 hours after the unlock)
+fn parse_personal_times(page: &str, year: u16) -> Vec<(u8, u8, DateTime<FixedOffset>)> {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let line = Regex::new(r"^\s*(?P<day>\d{1,2})\s+(?P<rest>.*)$").unwrap();
+    let time = Regex::new(r"^(?:(?P<h>\d+):(?P<m>\d{2}):(?P<s>\d{2})|>24h)$").unwrap();
+
+    let text = tags.replace_all(page, "").replace("&gt;", ">");
+    let mut solves = Vec::new();
+
+    for captures in text.lines().filter_map(|l| line.captures(l)) {
+        let Ok(day) = captures["day"].parse::<u8>() else {
+            continue;
+        };
+        if !(1..=dates::last_day(year)).contains(&day) {
+            continue;
+        }
+
+        // the first time belongs to part 1, the second one to part 2 (unsolved parts show a "-")
+        let times = captures["rest"]
+            .split_whitespace()
+            .filter_map(|token| time.captures(token))
+            .map(
+                |time| match (time.name("h"), time.name("m"), time.name("s")) {
+                    (Some(h), Some(m), Some(s)) => {
+                        Duration::hours(h.as_str().parse().unwrap())
+                            + Duration::minutes(m.as_str().parse().unwrap())
+                            + Duration::seconds(s.as_str().parse().unwrap())
+                    }
+                    _ => Duration::hours(24),
+                },
+            );
+
+        for (part, elapsed) in (1..=2).zip(times) {
+            solves.push((day, part, dates::unlock(year, day) + elapsed));
+        }
+    }
+
+    solves
+}
+
+// backfills the submission history with the parts solved before it was kept (according to the
+// personal times page of every selected year)
+pub async fn run(config: &Config, years: &[u16]) -> Result<()> {
+    let cookie = config.cookie_for("import the history")?;
+
+    for &year in years {
+        let page = web::get(cookie, &urls::personal_times(year)).await?;
+        let solves = parse_personal_times(&page, year);
+
+        let mut imported = 0;
+        for day in 1..=dates::last_day(year) {
+            let mut history = SubmissionHistory::load(&config.history, year, day)?;
+            let mut changed = false;
+
+            for &(_, part, solved_at) in solves.iter().filter(|(d, _, _)| *d == day) {
+                if history.backfill(part, solved_at) {
+                    imported += 1;
+                    changed = true;
+                }
+            }
+
+            if changed {
+                history.save()?;
+            }
+        }
+
+        println!(
+            "{}: {} stars, imported {} solves",
+            year,
+            solves.len(),
+            imported
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Output,
+};
+
+use crate::{clock, paths, storage, theme};
+
+// the full output of every run, kept in the "logs" directory of the state of the project (see
+// paths::project_state_dir), e.g.
+//
+// logs:
+//   keep: 20
+//   max_size: 4194304
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LogConfig {
+    // number of logs kept per project (older ones are removed)
+    pub keep: usize,
+    // maximum size of a single log in bytes, the middle of longer outputs is left out
+    pub max_size: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            keep: 10,
+            max_size: 1024 * 1024,
+        }
+    }
+}
+
+// the start and the end of the output, within the given number of bytes
+fn bounded(output: &[u8], max_size: usize) -> String {
+    if output.len() <= max_size {
+        return String::from_utf8_lossy(output).to_string();
+    }
+
+    let half = max_size / 2;
+    format!(
+        "{}\n[{} bytes left out]\n{}",
+        String::from_utf8_lossy(&output[..half]),
+        output.len() - 2 * half,
+        String::from_utf8_lossy(&output[output.len() - half..])
+    )
+}
+
+fn write(config: &LogConfig, dir: &Path, output: &Output) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let now = clock::now();
+    let path = dir.join(format!("run-{}.log", now.format("%Y%m%d-%H%M%S%.3f")));
+    let stream = |output: &[u8]| bounded(output, config.max_size / 2);
+    storage::write(
+        &path,
+        format!(
+            "# {} ({})\n\n## stdout\n{}\n## stderr\n{}",
+            now.to_rfc3339(),
+            output.status,
+            stream(&output.stdout),
+            stream(&output.stderr)
+        ),
+    )?;
+
+    // the names start with the time of the run, so the oldest ones come first
+    let mut logs: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("run-"))
+        })
+        .collect();
+    logs.sort();
+    for old in &logs[..logs.len().saturating_sub(config.keep)] {
+        fs::remove_file(old)?;
+    }
+
+    Ok(path)
+}
+
+// keeps the output of a run of the project, a failing log never fails the run, so it's only reported
+pub fn keep(config: &LogConfig, project_path: &Path, output: &Output) {
+    let dir = paths::project_state_dir(project_path).map(|dir| dir.join("logs"));
+    if let Err(e) = dir.and_then(|dir| write(config, &dir, output)) {
+        eprintln!(
+            "{}",
+            theme::skipped(&format!("failed to write the log of the run: {}", e))
+        );
+    }
+}
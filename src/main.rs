@@ -1,16 +1,86 @@
 use anyhow::{Result, anyhow};
-use aoc_api::Session;
-use clap::Parser;
 use colored::Colorize;
+use regex::Regex;
 use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
     fs,
-    process::{Command, Output},
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Output, Stdio},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
 mod args;
+mod art;
+mod audit;
+mod cache;
+mod check;
+mod clock;
 mod config;
-use args::{Args, Mode};
-use config::Config;
+mod countdown;
+mod custom;
+mod dates;
+mod editorconfig;
+mod freshness;
+mod harness;
+mod history;
+mod import;
+mod logs;
+mod manifest;
+mod matrix;
+mod metadata;
+mod metrics;
+mod ocr;
+mod output;
+mod outputs;
+mod paths;
+mod pending;
+mod perfstat;
+mod process;
+mod scaffold;
+mod scramble;
+mod sink;
+mod storage;
+mod submit;
+mod theme;
+mod urls;
+mod warnings;
+mod web;
+use args::{Args, Language, Mode};
+use cache::{Cache, Resource};
+use config::{Config, InputLocation};
+use editorconfig::EditorConfig;
+use history::SubmissionHistory;
+use manifest::Manifest;
+use matrix::{MatrixFormat, Status};
+use metadata::{Metadata, ProjectState, RunRecord};
+use output::{Answers, OutputStrategy, ReportedTime};
+use pending::PendingQueue;
+use perfstat::Recording;
+use submit::Verdict;
+use warnings::Warning;
+
+// the exit codes are documented in the help (see args.rs)
+const EXIT_SUCCESS: u8 = 0;
+const EXIT_FAILURE: u8 = 1;
+// exit code used when at least one submitted answer was wrong
+const EXIT_INCORRECT: u8 = 1;
+// exit code used when the build of the solution failed
+const EXIT_BUILD: u8 = 2;
+// exit code used when the solution failed (or couldn't be started)
+const EXIT_RUNTIME: u8 = 3;
+// exit code used for every other error (e.g. of the network or the config)
+const EXIT_ERROR: u8 = 4;
+// exit code used when some answers could not be submitted and were queued instead
+const EXIT_PARTIAL: u8 = 5;
+// exit code used when a run of multiple days was stopped with ctrl-c (like shells report SIGINT)
+const EXIT_INTERRUPTED: u8 = 130;
 
 #[macro_export]
 macro_rules! command {
@@ -29,6 +99,220 @@ macro_rules! command {
     };
 }
 
+// recursively copies a directory (skipping version control data)
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                copy_dir(&entry.path(), &target)?;
+            }
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+// a temporary copy of a project which is removed again when dropped
+struct IsolatedProject {
+    root: PathBuf,
+    project_path: PathBuf,
+}
+
+impl IsolatedProject {
+    fn create(config: &Config, input_file: &Path) -> Result<Self> {
+        let root = paths::temp_dir()?.join(format!("isolated-{}", std::process::id()));
+        let project_path = root.join(config.project_path.file_name().unwrap());
+
+        // inputs inside of the project are copied along with it and central ones stay where they are
+        copy_dir(&config.project_path, &project_path)
+            .map_err(|e| anyhow!("failed to copy project to '{}': {}", root.display(), e))?;
+        if config.input_location == InputLocation::Parent && input_file.exists() {
+            fs::copy(input_file, root.join("input.txt"))?;
+        }
+
+        Ok(Self { root, project_path })
+    }
+}
+
+impl Drop for IsolatedProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+// the stars earned for a day according to the calendar, falling back to the correct answers of
+// the history (e.g. offline or without a cookie)
+async fn day_stars(config: &Config, year: u16, day: u8) -> Result<u8> {
+    let calendar = match &config.cookie {
+        Some(cookie) => art::fetch_calendar(config, cookie, year)
+            .await
+            .ok()
+            .and_then(|page| art::parse_day_stars(&page).get(&day).copied()),
+        None => None,
+    };
+
+    match calendar {
+        Some(stars) => Ok(stars),
+        None => {
+            let history = SubmissionHistory::load(&config.history, year, day)?;
+            Ok((1..=2)
+                .filter_map(|part| history.get(part))
+                .filter(|record| record.verdict.is_correct())
+                .count() as u8)
+        }
+    }
+}
+
+// the values of the placeholders in scaffold templates, the title is only known once the puzzle
+// page could be downloaded (it's "Day 3" before that)
+async fn scaffold_placeholders(
+    config: &Config,
+    year: u16,
+    day: u8,
+    language: Language,
+) -> Result<scaffold::Placeholders> {
+    let title = match &config.cookie {
+        Some(cookie) => submit::puzzle_page(config, cookie, year, day)
+            .await
+            .ok()
+            .and_then(|page| submit::parse_title(&page)),
+        None => None,
+    };
+
+    Ok(scaffold::Placeholders::new(vec![
+        ("year", year.to_string()),
+        ("day", day.to_string()),
+        ("pad day", format!("{:0>2}", day)),
+        ("language", language.to_string()),
+        ("title", title.unwrap_or_else(|| format!("Day {}", day))),
+        (
+            "unlock_date",
+            dates::unlock(year, day).format("%Y-%m-%d").to_string(),
+        ),
+        (
+            "stars_earned",
+            day_stars(config, year, day).await?.to_string(),
+        ),
+    ]))
+}
+
+// offers ways to look into a failed run, until the day is run again (true) or the menu is left
+fn failure_menu(config: &Config, args: &Args) -> Result<bool> {
+    let (year, day) = (args.year.unwrap(), args.day.unwrap());
+    let example = config.input_file(year, day)?.with_file_name("example.txt");
+
+    loop {
+        print!(
+            "[r]erun, [e]dit, show [s]tderr, {}[d]iff against the last successful output \
+             or [q]uit? ",
+            match example.exists() {
+                true => "run the e[x]ample, ",
+                false => "",
+            }
+        );
+        io::stdout().flush()?;
+
+        let mut choice = String::new();
+        if io::stdin().read_line(&mut choice)? == 0 {
+            return Ok(false);
+        }
+
+        match choice.trim().to_lowercase().as_str() {
+            "r" | "rerun" => return Ok(true),
+            "e" | "edit" => open_editor(config)?,
+            "s" | "stderr" => match outputs::last_stderr(args)? {
+                Some(stderr) if !stderr.trim().is_empty() => eprintln!("{}", stderr.trim_end()),
+                _ => println!("{}", theme::skipped("the last run didn't write to stderr")),
+            },
+            "x" | "example" if example.exists() => {
+                match execute_solution(config, args, &example, None) {
+                    Ok(Execution {
+                        stdout, elapsed, ..
+                    }) => {
+                        let extractor = Metadata::load(&config.project_path)?.extractor(config)?;
+                        for (part, answer) in extractor.parse(&stdout).unwrap_or_default().parts() {
+                            if let Some(answer) = answer {
+                                println!("part {} of the example: {}", part, answer);
+                            }
+                        }
+                        eprintln!("{}", theme::timing(&timing(elapsed, None, 0)));
+                    }
+                    Err(e) => eprintln!("{}", theme::incorrect(&format!("error: {:#}", e))),
+                }
+            }
+            "d" | "diff" => match outputs::diff(args)? {
+                Some(diff) if diff.is_empty() => {
+                    println!(
+                        "{}",
+                        theme::skipped("the output matches the last successful one")
+                    )
+                }
+                Some(diff) => print!("{}", diff),
+                None => println!(
+                    "{}",
+                    theme::skipped("there's no successful output to compare with")
+                ),
+            },
+            "q" | "quit" | "" => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+// opens the project in $VISUAL or $EDITOR (waiting for terminal editors to be closed), otherwise in
+// vs code like code mode
+fn open_editor(config: &Config) -> Result<()> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty());
+
+    match editor {
+        Some(editor) => {
+            let mut words = editor.split_whitespace();
+            let mut command = command!(words.next().unwrap());
+            command.args(words).arg(&config.project_path).status()?;
+        }
+        None => {
+            command!("code", &config.project_path).spawn()?;
+        }
+    }
+
+    Ok(())
+}
+
+// asks a yes/no question on the terminal (defaulting to no)
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+// formats a duration with a unit fitting its magnitude
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+
+    if seconds >= 1.0 {
+        format!("{:.2}s", seconds)
+    } else if seconds >= 0.001 {
+        format!("{:.2}ms", seconds * 1000.0)
+    } else {
+        let unit = if theme::ascii() { "us" } else { "µs" };
+        format!("{:.2}{}", seconds * 1_000_000.0, unit)
+    }
+}
+
 pub fn eval_command_output(output: &Output, silent: bool) -> Result<()> {
     match output.status.success() {
         true => {
@@ -44,164 +328,2283 @@ pub fn eval_command_output(output: &Output, silent: bool) -> Result<()> {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let (mut config, optional_parameters) = Config::load()?;
-    let mut args = Args::parse();
+fn cache_mode(config: &Config, params: &[String]) -> Result<u8> {
+    let cache = Cache::open(&config.cache)?;
 
-    args.build(optional_parameters);
-    config.build(&args)?;
+    match params.first().map(|p| p.as_str()) {
+        Some("clear") => {
+            // optionally only clear a single resource (e.g. "aoc cache clear puzzle")
+            let resource = params.get(1).map(|r| r.parse()).transpose()?;
+            let freed = cache.clear(resource)?;
+            println!("freed {}", cache::format_bytes(freed));
+        }
+        Some("stats") | None => {
+            let stats = cache.stats();
 
-    // throw error if modes run, init, path, code are used without a language
-    if matches!(args.mode, Mode::Run | Mode::Init | Mode::Path | Mode::Code)
-        && args.language.is_none()
-    {
-        return Err(anyhow!("language is required for mode '{:?}'", args.mode));
+            println!("{}", cache.root().display());
+            for stat in &stats {
+                println!(
+                    "{:<12} {:>5} files {:>12}",
+                    stat.resource.to_string(),
+                    stat.files,
+                    cache::format_bytes(stat.bytes)
+                );
+            }
+            println!(
+                "{:<12} {:>5} files {:>12} / {}",
+                "total",
+                stats.iter().map(|s| s.files).sum::<usize>(),
+                cache::format_bytes(stats.iter().map(|s| s.bytes).sum()),
+                cache::format_bytes(cache.max_size())
+            );
+        }
+        Some(action) => {
+            return Err(anyhow!("unknown cache action: {}", action));
+        }
     }
 
-    // throw error if project doesn't exist for modes that require existence
-    if matches!(args.mode, Mode::Run | Mode::Code) && !config.project_path.exists() {
-        return Err(anyhow!(
-            "project does not exist: {}",
-            config.project_path.display()
-        ));
-    }
+    Ok(EXIT_SUCCESS)
+}
 
-    let session = config
-        .cookie
-        .as_ref()
-        .map(|cookie| Session::new(cookie.clone(), args.year.unwrap(), args.day.unwrap()));
+// stores the session cookie (given or asked for) in the config, in place of a previous one
+fn login_mode(params: &[String]) -> Result<u8> {
+    let cookie = match params.first() {
+        Some(cookie) => cookie.clone(),
+        None => {
+            eprintln!(
+                "paste the value of the 'session' cookie of adventofcode.com \
+                 (see the developer tools of your browser)"
+            );
+            print!("cookie: ");
+            io::stdout().flush()?;
 
-    // check for input file and download if necessary
-    if matches!(args.mode, Mode::Run | Mode::Init) {
-        let parent_path = config
-            .project_path
-            .parent()
-            .ok_or(anyhow!("project path does not have a parent directory"))?;
-        let input_file = parent_path.join("input.txt");
-
-        if !input_file.exists() {
-            if let Some(session) = &session {
-                fs::create_dir_all(parent_path)?;
-                fs::write(
-                    &input_file,
-                    session
-                        .get_input_text()
-                        .await
-                        .map_err(|e| anyhow!("{}", e))?,
-                )?;
-            }
+            let mut cookie = String::new();
+            io::stdin().read_line(&mut cookie)?;
+            cookie
         }
+    };
+    let cookie = cookie.trim();
+    let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+    if cookie.is_empty() {
+        return Err(anyhow!("no cookie given"));
     }
 
-    match args.mode {
-        Mode::Run => {
-            // run build (if exists for given language) command silently (meaning stdout is not printed)
-            args.language
-                .unwrap()
-                .build_command(&config)
-                .map(|mut cmd| eval_command_output(&cmd.output()?, true))
-                .transpose()?;
-
-            let run_output = args.language.unwrap().run_command(&config).output()?;
-            eval_command_output(&run_output, true)?;
-
-            let stdout = String::from_utf8_lossy(&run_output.stdout).to_string();
-
-            // create session if cookie is provided
-            if let Some(session) = &session {
-                // count number of \n to determine number of parts to validate
-                let new_lines: Vec<usize> = stdout
-                    .chars()
-                    .enumerate()
-                    .filter_map(|(i, c)| if c == '\n' { Some(i) } else { None })
-                    .collect();
+    let config_file = paths::config_file()?;
+    let content = match fs::read_to_string(&config_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
 
-                // newlines must be:
-                // 1 = first part
-                // 2 = first and second part
-                if (1..=2).contains(&new_lines.len()) {
-                    // split stdout into parts based on newlines
-                    let (part1, part2) = match new_lines.len() {
-                        1 => (stdout.trim_end(), None),
-                        2 => {
-                            let (part1, part2) = stdout.split_at(new_lines[0]);
-                            (part1, Some(part2[1..].trim_end()))
-                        }
-                        _ => unreachable!(),
-                    };
+    // only the top-level key is replaced, the rest of the config is kept as it is
+    let line = format!("cookie: {}", serde_json::to_string(cookie)?);
+    let re = Regex::new(r"(?m)^cookie:.*$").unwrap();
+    let content = match re.is_match(&content) {
+        true => re.replace(&content, regex::NoExpand(&line)).to_string(),
+        false if content.is_empty() || content.ends_with('\n') => format!("{}{}\n", content, line),
+        false => format!("{}\n{}\n", content, line),
+    };
 
-                    let part1_success = session
-                        .submit_answer_explicit_error(1, part1)
-                        .await
-                        .map_err(|e| anyhow!("{e}"))?;
+    if let Some(parent) = config_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    storage::write(&config_file, content)?;
+    paths::restrict_to_owner(&config_file)?;
+
+    println!("stored the cookie in '{}'", config_file.display());
+    Ok(EXIT_SUCCESS)
+}
 
+async fn pending_mode(config: &Config, params: &[String]) -> Result<u8> {
+    let mut pending = PendingQueue::load()?;
+
+    match params.first().map(|p| p.as_str()) {
+        Some("submit") => {
+            let cookie = config.cookie_for("submit pending answers")?;
+            let mut remaining = Vec::new();
+            let mut queue = std::mem::take(&mut pending.submissions).into_iter();
+
+            while let Some(submission) = queue.next() {
+                let label = format!(
+                    "{}/{:0>2} part {}",
+                    submission.year, submission.day, submission.part
+                );
+                let mut history =
+                    SubmissionHistory::load(&config.history, submission.year, submission.day)?;
+
+                // the same checks as for a normal submission, queued answers which were judged in
+                // the meantime (or whose part has been solved since) are dropped
+                let page_answers =
+                    submit::accepted_answers(config, cookie, submission.year, submission.day)
+                        .await
+                        .unwrap_or_default();
+                let solved = |part: u8| {
+                    history
+                        .get(part)
+                        .is_some_and(|record| record.verdict.is_correct())
+                        || page_answers.len() >= part as usize
+                };
+                if solved(submission.part) {
                     println!(
-                        "{}",
-                        if part1_success {
-                            part1.green()
-                        } else {
-                            part1.red()
-                        }
+                        "{}: {} (previously solved, dropped)",
+                        label,
+                        theme::skipped(&submission.answer)
+                    );
+                    continue;
+                }
+                if let Some(verdict) = history
+                    .verdict(submission.part, &submission.answer)
+                    .map(Verdict::without_wait)
+                {
+                    println!(
+                        "{}: {} (submitted before: {}, dropped)",
+                        label,
+                        verdict.colorize(&submission.answer),
+                        verdict
                     );
+                    continue;
+                }
+                if submission.part == 2 && !solved(1) {
+                    eprintln!("{}: not submitted: part 1 hasn't been accepted", label);
+                    remaining.push(submission);
+                    continue;
+                }
+
+                let result = submit::submit_answer(
+                    cookie,
+                    submission.year,
+                    submission.day,
+                    submission.part,
+                    &submission.answer,
+                )
+                .await;
 
-                    // continue to part 2 if it exists
-                    if let Some(part2) = part2 {
-                        let part2_success = session
-                            .submit_answer_explicit_error(2, part2)
-                            .await
-                            .map_err(|e| anyhow!("{e}"))?;
+                if let Ok(verdict) = &result {
+                    history.record(submission.part, &submission.answer, verdict);
+                    history.save()?;
+                }
 
+                match result {
+                    Ok(verdict) if !matches!(verdict, Verdict::RateLimited { .. }) => {
                         println!(
-                            "{}",
-                            if part2_success {
-                                part2.green()
-                            } else {
-                                part2.red()
-                            }
-                        );
+                            "{}: {} ({})",
+                            label,
+                            verdict.colorize(&submission.answer),
+                            verdict
+                        )
+                    }
+                    // the rest of the queue would only run into the same cooldown
+                    Ok(verdict) => {
+                        eprintln!("{}: failed to submit: {}", label, verdict);
+                        remaining.push(submission);
+                        remaining.extend(queue);
+                        break;
+                    }
+                    Err(e) => {
+                        eprintln!("{}: failed to submit: {}", label, e);
+                        remaining.push(submission);
                     }
-
-                    // validation was successful
-                    // exit successfully to prevent further output
-                    return Ok(());
                 }
             }
 
-            // if no session is provided or newlines are not 1 or 2, just print the output
-            println!("{}", String::from_utf8_lossy(&run_output.stdout));
+            pending.submissions = remaining;
+            pending.save()?;
+
+            if !pending.submissions.is_empty() {
+                return Ok(EXIT_PARTIAL);
+            }
         }
-        Mode::Init => {
-            // throw error if trying to initialize but project already exists
-            if config.project_path.exists() {
-                return Err(anyhow!(
-                    "project already exists: {}",
-                    config.project_path.display()
-                ));
+        Some("list") | None => {
+            for submission in &pending.submissions {
+                println!(
+                    "{}/{:0>2} part {}: {} ({}, queued at {})",
+                    submission.year,
+                    submission.day,
+                    submission.part,
+                    submission.answer,
+                    submission.reason,
+                    submission.queued_at
+                );
+            }
+        }
+        Some(action) => {
+            return Err(anyhow!("unknown pending action: {}", action));
+        }
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+// prints the earned stars for every selected year
+async fn stats_mode(config: &Config, years: &[u16]) -> Result<u8> {
+    let cookie = config.cookie_for("retrieve stats")?;
+    let cache = Cache::open(&config.cache)?;
+
+    let key = cache::account_key(cookie, "stars");
+    let stars: HashMap<u16, u8> = match cache
+        .get(Resource::Completion, &key)
+        .and_then(|content| serde_json::from_str(&content).ok())
+    {
+        Some(stars) => stars,
+        None => {
+            let stars = web::stars(cookie).await?;
+            cache.put(Resource::Completion, &key, &serde_json::to_string(&stars)?)?;
+            stars
+        }
+    };
+
+    let mut total = 0;
+    for year in years {
+        let earned = stars.get(year).copied().unwrap_or(0);
+        total += earned as u32;
+
+        println!(
+            "{}: {:>2}/50 {}",
+            year,
+            earned,
+            if earned == 50 {
+                theme::skipped(&"*".repeat(25))
             } else {
-                fs::create_dir_all(&config.project_path)
-                    .map_err(|e| anyhow!("failed to create project directory: {}", e))?;
+                "*".repeat(earned as usize / 2).normal()
             }
+        );
+    }
+    println!("total: {}/{}", total, years.len() * 50);
 
-            eval_command_output(
-                &args.language.unwrap().init_command(&config).output()?,
-                false,
-            )?;
+    Ok(EXIT_SUCCESS)
+}
+
+// prints the calendar art of every selected year with the earned stars highlighted
+async fn art_mode(config: &Config, years: &[u16]) -> Result<u8> {
+    let cookie = config.cookie_for("retrieve the calendar")?;
+
+    for &year in years {
+        let page = art::fetch_calendar(config, cookie, year).await?;
+
+        println!(
+            "{}",
+            art::render_calendar(&page)
+                .ok_or_else(|| anyhow!("could not find the calendar of {}", year))?
+        );
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+// prints the languages with an existing project for every selected day
+fn list_mode(config: &mut Config, args: &mut Args, years: &[u16], days: &[u8]) -> Result<u8> {
+    // only list the given language, if one was selected
+    // (templates without a language parameter only have one project per day)
+    let languages: Vec<Option<Language>> = if !config.uses_parameter("language") {
+        vec![None]
+    } else if !args.languages.is_empty() {
+        args.languages.iter().copied().map(Some).collect()
+    } else if let Some(language) = args.language {
+        vec![Some(language)]
+    } else {
+        Language::all().into_iter().map(Some).collect()
+    };
+
+    for &year in years {
+        for &day in days {
+            args.year = Some(year);
+            args.day = Some(day);
+
+            let mut existing = Vec::new();
+            for &language in &languages {
+                args.language = language;
+                config.build(args)?;
+
+                if !config.project_path.exists() {
+                    continue;
+                }
+
+                // only include projects having all of the requested tags
+                let tags = Metadata::load(&config.project_path)?.tags;
+                if !args
+                    .tags
+                    .iter()
+                    .all(|tag| tags.contains(&tag.to_lowercase()))
+                {
+                    continue;
+                }
+
+                let name = language.map_or("exists".to_string(), |l| l.to_string());
+                existing.push(match tags.is_empty() {
+                    true => name,
+                    false => format!("{} [{}]", name, tags.join(", ")),
+                });
+            }
+
+            if !existing.is_empty() {
+                println!("{}/{:0>2}: {}", year, day, existing.join(", "));
+            }
         }
-        Mode::Path => {
-            println!("{}", config.project_path.display());
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+// pre-downloads the dependencies of every existing project of the selected days
+fn vendor_mode(config: &mut Config, args: &mut Args, years: &[u16], days: &[u8]) -> Result<u8> {
+    let languages: Vec<Option<Language>> = if !config.uses_parameter("language") {
+        vec![None]
+    } else if !args.languages.is_empty() {
+        args.languages.iter().copied().map(Some).collect()
+    } else {
+        Language::all().into_iter().map(Some).collect()
+    };
+
+    let mut failed = false;
+
+    for &year in years {
+        for &day in days {
+            args.year = Some(year);
+            args.day = Some(day);
+
+            for &language in &languages {
+                args.language = language;
+                config.build(args)?;
+
+                if !config.project_path.exists() {
+                    continue;
+                }
+
+                // templates without a language parameter may contain a project of any language
+                let project_languages: Vec<Language> = match language {
+                    Some(language) => vec![language],
+                    None => Language::all()
+                        .into_iter()
+                        .filter(|language| language.is_project(&config.project_path))
+                        .collect(),
+                };
+
+                for language in project_languages {
+                    let Some(mut command) = language.vendor_command(config) else {
+                        continue;
+                    };
+
+                    print!("{}/{:0>2} {}: ", year, day, language);
+                    match eval_command_output(&command.output()?, true) {
+                        Ok(()) => println!("{}", theme::correct("vendored")),
+                        Err(e) => {
+                            failed = true;
+                            // the error already starts with "failed: "
+                            println!("{}", theme::incorrect(format!("{:#}", e).trim_end()));
+                        }
+                    }
+                }
+            }
         }
-        Mode::Code => {
-            command!("code", &config.project_path).spawn()?;
+    }
+
+    Ok(if failed { EXIT_FAILURE } else { EXIT_SUCCESS })
+}
+
+// runs every selected day in every selected language and prints the results as a grid, the
+// answers are compared with the accepted ones of the history (nothing is submitted)
+async fn matrix_mode(
+    config: &mut Config,
+    args: &mut Args,
+    years: &[u16],
+    days: &[u8],
+) -> Result<u8> {
+    let selected = !args.languages.is_empty();
+    let languages: Vec<Language> = match selected {
+        true => args.languages.clone(),
+        false => Language::all(),
+    };
+    let format = match args.json {
+        true => MatrixFormat::Json,
+        false => args.format,
+    };
+    let mut cells = Vec::new();
+
+    for &year in years {
+        for &day in days {
+            args.year = Some(year);
+            args.day = Some(day);
+
+            for &language in &languages {
+                args.language = Some(language);
+                config.build(args)?;
+                if args.redo {
+                    *config = config.with_project_path(redo_variant(&config.project_path));
+                }
+
+                // templates without a language parameter may contain a project of any language
+                let exists = config.project_path.exists()
+                    && (config.uses_parameter("language")
+                        || language.is_project(&config.project_path));
+                if !exists {
+                    cells.push(matrix::Cell::new(
+                        year,
+                        day,
+                        language.to_string(),
+                        Status::Missing,
+                    ));
+                    continue;
+                }
+
+                let cell = matrix_cell(config, args).await?;
+                eprintln!("{}/{:0>2} {}: {}", year, day, language, cell.summary());
+                // the summary of a timeout says it all
+                if let Some(error) = &cell.error
+                    && cell.status != Status::TimedOut
+                {
+                    eprintln!("{}", theme::incorrect(error.trim_end()));
+                }
+                cells.push(cell);
+            }
         }
-        Mode::Url => {
-            println!(
-                "https://adventofcode.com/{}/day/{}",
-                args.year.unwrap(),
-                args.day.unwrap()
+    }
+
+    // languages without any projects are left out, unless they were selected
+    let columns: Vec<String> = languages
+        .iter()
+        .map(Language::to_string)
+        .filter(|language| {
+            selected
+                || cells
+                    .iter()
+                    .any(|cell| &cell.language == language && cell.status != Status::Missing)
+        })
+        .collect();
+    // as are days without any projects
+    let rows: Vec<(u16, u8)> = cells
+        .iter()
+        .filter(|cell| cell.status != Status::Missing)
+        .map(|cell| (cell.year, cell.day))
+        .collect();
+    cells.retain(|cell| columns.contains(&cell.language) && rows.contains(&(cell.year, cell.day)));
+    print!("{}", matrix::render(&cells, &columns, format));
+
+    let failed = cells.iter().any(|cell| {
+        !matches!(
+            cell.status,
+            Status::Passed | Status::Unverified | Status::Missing
+        )
+    });
+    Ok(if failed { EXIT_FAILURE } else { EXIT_SUCCESS })
+}
+
+// runs the project of the current day and compares its answers with the accepted ones
+async fn matrix_cell(config: &Config, args: &Args) -> Result<matrix::Cell> {
+    let (year, day, language) = (
+        args.year.unwrap(),
+        args.day.unwrap(),
+        args.language.unwrap(),
+    );
+    let cell = |status| matrix::Cell::new(year, day, language.to_string(), status);
+
+    let input_file = config.input_file(year, day)?;
+    if !input_file.exists() {
+        let Some(cookie) = &config.cookie else {
+            return Ok(cell(Status::NoInput));
+        };
+        let input = match official_input(config, cookie, year, day).await {
+            Ok(input) => input,
+            Err(e) => {
+                return Ok(matrix::Cell {
+                    error: Some(e.to_string()),
+                    ..cell(Status::NoInput)
+                });
+            }
+        };
+
+        if let Some(parent) = input_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        storage::write(&input_file, input)?;
+    }
+
+    let Execution {
+        stdout,
+        elapsed: measured,
+        ..
+    } = match execute_solution(config, args, &input_file, None) {
+        Ok(execution) => execution,
+        Err(e) => {
+            let status = match e.downcast_ref::<SolutionError>() {
+                Some(SolutionError::Build(_)) => Status::BuildFailed,
+                Some(SolutionError::Run(e)) if e.is::<process::TimedOut>() => Status::TimedOut,
+                Some(SolutionError::Run(_)) => Status::RunFailed,
+                None => return Err(e),
+            };
+            return Ok(matrix::Cell {
+                error: Some(format!("{:#}", e)),
+                ..cell(status)
+            });
+        }
+    };
+
+    let extractor = Metadata::load(&config.project_path)?.extractor(config)?;
+    let reported = extractor.reported_time(&stdout);
+    let elapsed = reported
+        .as_ref()
+        .map_or(measured, |reported| reported.total);
+
+    // the last day only has a single puzzle
+    let part = (day == dates::last_day(year)).then_some(1);
+    let answers = extractor
+        .parse(&stdout)
+        .map(|answers| answers.select(part).normalize(&config.normalize))
+        .unwrap_or_default();
+
+    let history = SubmissionHistory::load(&config.history, year, day)?;
+    let parts: Vec<matrix::Part> = answers
+        .parts()
+        .into_iter()
+        .filter(|(part, _)| *part == 1 || day != dates::last_day(year))
+        .map(|(part, answer)| matrix::Part {
+            part,
+            answer: answer.map(str::to_string),
+            accepted: history
+                .get(part)
+                .filter(|record| record.verdict.is_correct() && !record.answer.is_empty())
+                .map(|record| record.answer.clone()),
+        })
+        .collect();
+
+    let status = if parts
+        .iter()
+        .any(|part| part.accepted.is_some() && part.answer != part.accepted)
+    {
+        Status::Failed
+    } else if parts.iter().any(|part| part.accepted.is_some()) {
+        Status::Passed
+    } else {
+        Status::Unverified
+    };
+
+    Ok(matrix::Cell {
+        parts,
+        ..cell(status).with_time(elapsed)
+    })
+}
+
+// a successful run of a solution
+struct Execution {
+    stdout: String,
+    // wall clock time of the measured run
+    elapsed: Duration,
+    // none without a build step
+    build_time: Option<Duration>,
+    // peak resident memory in bytes, none where it isn't measured
+    peak_memory: Option<u64>,
+}
+
+// a failure of the solution itself, told apart from errors of the tool by the exit code
+#[derive(Debug)]
+enum SolutionError {
+    Build(anyhow::Error),
+    Run(anyhow::Error),
+}
+
+impl std::fmt::Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolutionError::Build(e) | SolutionError::Run(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
+impl std::error::Error for SolutionError {}
+
+fn exit_code(error: &anyhow::Error) -> u8 {
+    match error.downcast_ref::<SolutionError>() {
+        Some(SolutionError::Build(_)) => EXIT_BUILD,
+        Some(SolutionError::Run(_)) => EXIT_RUNTIME,
+        None => EXIT_ERROR,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // usage errors are errors of the tool as well (clap would exit with the code of failed builds)
+    let args = Args::try_parse().unwrap_or_else(|e| {
+        let _ = e.print();
+        std::process::exit(match e.use_stderr() {
+            true => EXIT_ERROR.into(),
+            false => EXIT_SUCCESS.into(),
+        })
+    });
+
+    match run(args).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+async fn run(mut args: Args) -> Result<ExitCode> {
+    // the parameters are extracted from the current directory, so it has to be changed beforehand
+    if let Some(cwd) = &args.cwd {
+        env::set_current_dir(cwd)
+            .map_err(|e| anyhow!("failed to change to '{}': {}", cwd.display(), e))?;
+    }
+    // the cookie may be stored before there's anything else in the config
+    if args.mode == Mode::Login {
+        return login_mode(&args.params).map(ExitCode::from);
+    }
+    let (mut config, optional_parameters) = Config::load()?;
+
+    if let Some(fake_now) = args.fake_now {
+        clock::fake_now(fake_now);
+    }
+    web::configure(&config.network);
+    urls::configure(&config.network.base_url);
+    theme::configure(&config.theme)?;
+    warnings::configure(config.allow.iter().chain(&args.allow));
+    if let Some(profile) = args.execution.profile {
+        config.profile = profile;
+    }
+
+    // on shared machines anyone able to read the config could act as its owner
+    let config_file = paths::config_file()?;
+    if config.cookie.is_some() && paths::readable_by_others(&config_file) {
+        warnings::warn(
+            Warning::ReadableCookie,
+            &format!(
+                "'{}' contains the session cookie but is readable by other users \
+                 (restrict it with 'chmod 600')",
+                config_file.display()
+            ),
+        );
+    }
+
+    if config.cookie.is_none() {
+        if config.require_cookie && args.mode.uses_cookie() {
+            return Err(anyhow!(
+                "no cookie is configured, but 'require_cookie' is set \
+                 (store yours with 'aoc login')"
+            ));
+        }
+        if matches!(args.mode, Mode::Run | Mode::Init) {
+            warnings::warn(
+                Warning::NoCookie,
+                "no cookie is configured, so inputs aren't downloaded and answers aren't \
+                 submitted (store yours with 'aoc login')",
             );
         }
     }
 
-    Ok(())
+    // running the solution of another day from within a project directory is most likely a mistake
+    // (which could waste a submission)
+    if let Some(mismatch) = args.directory_mismatch(&optional_parameters) {
+        if matches!(args.mode, Mode::Run | Mode::Submit) && !args.force {
+            return Err(anyhow!("{} (pass '--force' to run anyway)", mismatch));
+        }
+        warnings::warn(Warning::DirectoryMismatch, &mismatch);
+    }
+
+    let (years, days) = args.build(optional_parameters)?;
+
+    if args.languages.len() > 1
+        && !matches!(
+            args.mode,
+            Mode::Init | Mode::List | Mode::Vendor | Mode::Matrix
+        )
+    {
+        return Err(anyhow!(
+            "multiple languages are only supported by modes 'Init', 'List', 'Vendor' and 'Matrix'"
+        ));
+    }
+
+    // an answer belongs to a single puzzle
+    if args.mode == Mode::Submit && (years.len() > 1 || days.len() > 1) {
+        return Err(anyhow!("submit mode requires a single day"));
+    }
+
+    // modes which don't operate on a specific day
+    match args.mode {
+        Mode::Cache => return cache_mode(&config, &args.params).map(ExitCode::from),
+        Mode::Pending => {
+            return pending_mode(&config, &args.params)
+                .await
+                .map(ExitCode::from);
+        }
+        Mode::Stats => {
+            // without an explicit year selection, show the whole history
+            let years = if args.years.is_none() && !args.all_years {
+                (2015..=args::latest_year()).collect()
+            } else {
+                years
+            };
+            return stats_mode(&config, &years).await.map(ExitCode::from);
+        }
+        Mode::Metrics => {
+            let years = if args.years.is_none() && !args.all_years {
+                (2015..=args::latest_year()).collect()
+            } else {
+                years
+            };
+            match args.listen {
+                Some(address) => metrics::serve(&config, &years, address).await?,
+                None => print!("{}", metrics::render(&config, &years)?),
+            }
+            return Ok(ExitCode::SUCCESS);
+        }
+        Mode::Answers => {
+            // without an explicit year selection, cover the whole history
+            let years = if args.years.is_none() && !args.all_years {
+                (2015..=args::latest_year()).collect()
+            } else {
+                years
+            };
+            match args.export {
+                Some(format) => print!("{}", audit::export(&config, &years, format)?),
+                None => print!("{}", audit::list(&config, &years)?),
+            }
+            return Ok(ExitCode::SUCCESS);
+        }
+        Mode::ImportHistory => {
+            // without an explicit year selection, import the whole history
+            let years = if args.years.is_none() && !args.all_years {
+                (2015..=args::latest_year()).collect()
+            } else {
+                years
+            };
+            import::run(&config, &years).await?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Mode::Countdown => {
+            countdown::run(&config).await?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Mode::Art => return art_mode(&config, &years).await.map(ExitCode::from),
+        Mode::List => {
+            // without an explicit day selection, list every day of the selected years
+            let days = if args.days.is_none() {
+                (1..=25).collect()
+            } else {
+                days
+            };
+            return list_mode(&mut config, &mut args, &years, &days).map(ExitCode::from);
+        }
+        Mode::Matrix => {
+            // without an explicit day selection, run every day of the selected years
+            let days = if args.days.is_none() {
+                (1..=25).collect()
+            } else {
+                days
+            };
+            return matrix_mode(&mut config, &mut args, &years, &days)
+                .await
+                .map(ExitCode::from);
+        }
+        Mode::Vendor => {
+            // without an explicit day selection, vendor every initialized day of the selected years
+            let days = if args.days.is_none() {
+                (1..=25).collect()
+            } else {
+                days
+            };
+            return vendor_mode(&mut config, &mut args, &years, &days).map(ExitCode::from);
+        }
+        _ => {}
+    }
+
+    if let ([year], [day]) = (&years[..], &days[..]) {
+        args.year = Some(*year);
+        args.day = Some(*day);
+        build_project(&mut config, &mut args)?;
+
+        loop {
+            let result = run_day(&config, &args).await;
+            let code = result.as_ref().map_or_else(exit_code, |code| *code);
+            if args.mode == Mode::Run && args.input.is_none() && code == EXIT_SUCCESS {
+                outputs::keep_success(&args)?;
+            }
+
+            // failed builds, failed runs and wrong answers on a terminal can be looked into at once
+            let interactive = args.mode == Mode::Run
+                && !args.json
+                && io::stdin().is_terminal()
+                && io::stdout().is_terminal();
+            if !interactive || !matches!(code, EXIT_BUILD | EXIT_RUNTIME | EXIT_INCORRECT) {
+                return result.map(ExitCode::from);
+            }
+
+            // the error isn't returned, so it's shown before the menu
+            if let Err(e) = &result {
+                eprintln!("{}", theme::incorrect(&format!("error: {:#}", e)));
+            }
+            if !failure_menu(&config, &args)? {
+                return Ok(ExitCode::from(code));
+            }
+        }
+    }
+
+    // run every selected day on its own and print a combined report at the end
+    let mut report = Vec::new();
+    let language = args.language;
+
+    // ctrl-c stops launching further days, but the days run so far are still reported
+    // (their results have already been recorded and written to the sinks)
+    let interrupted = Arc::new(AtomicBool::new(false));
+    tokio::spawn({
+        let interrupted = interrupted.clone();
+        async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                interrupted.store(true, Ordering::SeqCst);
+            }
+        }
+    });
+
+    'days: for &year in &years {
+        for &day in &days {
+            if interrupted.load(Ordering::SeqCst) {
+                break 'days;
+            }
+
+            args.year = Some(year);
+            args.day = Some(day);
+            // every day infers its own language, if none was given
+            args.language = language;
+            println!("{}", format!("{}/{:0>2}", year, day).bold());
+
+            let result = match build_project(&mut config, &mut args) {
+                Ok(()) => run_day(&config, &args).await,
+                Err(e) => Err(e),
+            };
+            // the solution received the signal as well, so its failure isn't a real one
+            let result = match result {
+                Err(_) if interrupted.load(Ordering::SeqCst) => Err(anyhow!("interrupted")),
+                result => result,
+            };
+
+            if let Err(e) = &result {
+                eprintln!("{}", theme::incorrect(&format!("error: {}", e)));
+            }
+            report.push((year, day, result));
+        }
+    }
+
+    let interrupted = interrupted.load(Ordering::SeqCst);
+    let skipped = years.len() * days.len() - report.len();
+
+    println!("{}", "summary".bold());
+    for (year, day, result) in &report {
+        println!(
+            "{}/{:0>2}: {}",
+            year,
+            day,
+            match result {
+                Ok(EXIT_SUCCESS) => theme::correct("ok"),
+                Ok(code) => theme::skipped(&format!("exit code {}", code)),
+                Err(e) => theme::incorrect(&e.to_string()),
+            }
+        );
+    }
+
+    if interrupted {
+        eprintln!(
+            "{}",
+            theme::skipped(&format!(
+                "interrupted, {} selected days were not run",
+                skipped
+            ))
+        );
+        return Ok(ExitCode::from(EXIT_INTERRUPTED));
+    }
+
+    // exit with the first non-successful code (errors count as generic failures)
+    Ok(ExitCode::from(
+        report
+            .into_iter()
+            .map(|(_, _, result)| result.unwrap_or_else(|e| exit_code(&e)))
+            .find(|code| *code != EXIT_SUCCESS)
+            .unwrap_or(EXIT_SUCCESS),
+    ))
+}
+
+// submits the extracted answers, prints their verdicts and returns the resulting exit code
+async fn submit_answers(
+    config: &Config,
+    args: &Args,
+    cookie: &str,
+    answers: &Answers,
+    elapsed: Duration,
+    confirmed: bool,
+) -> Result<u8> {
+    let answers = &answers.normalize(&config.normalize);
+    let mut pending = PendingQueue::load()?;
+    let mut history =
+        SubmissionHistory::load(&config.history, args.year.unwrap(), args.day.unwrap())?;
+    let mut queued = false;
+    // whether queued answers became stale (their part was solved or another answer was judged)
+    let mut dequeued = false;
+    let mut incorrect = false;
+    let mut report = Vec::new();
+    // part 2 can't be answered before part 1 has been accepted, so it isn't sent while part 1 is
+    // wrong (in this run or, if it isn't answered again, the last time) or wasn't submitted
+    let mut unaccepted = history
+        .get(1)
+        .is_some_and(|record| record.verdict.is_incorrect())
+        .then_some("part 1 hasn't been accepted");
+    let mut page_answers: Option<Vec<String>> = None;
+
+    for (part, answer) in answers.parts() {
+        let Some(answer) = answer else {
+            continue;
+        };
+
+        // the answers accepted so far are listed on the puzzle page, it's only needed if the
+        // history doesn't know the accepted answer (a failed download just means unknown)
+        let recorded = history
+            .get(part)
+            .filter(|record| record.verdict.is_correct())
+            .map(|record| record.answer.clone());
+        if recorded.as_ref().is_none_or(String::is_empty) && page_answers.is_none() {
+            page_answers = Some(
+                submit::accepted_answers(config, cookie, args.year.unwrap(), args.day.unwrap())
+                    .await
+                    .unwrap_or_default(),
+            );
+        }
+        let accepted = match recorded {
+            Some(answer) if !answer.is_empty() => Some(Some(answer)),
+            recorded => match page_answers.iter().flatten().nth(part as usize - 1) {
+                Some(answer) => Some(Some(answer.clone())),
+                None => recorded.map(|_| None),
+            },
+        };
+
+        // parts accepted before aren't submitted again (the server wouldn't judge them anyway),
+        // the answer is compared with the accepted one instead and the output focuses on the part
+        // still being solved
+        if let Some(accepted) = accepted {
+            let verdict = Verdict::AlreadySolved {
+                matches: accepted.as_ref().map(|accepted| accepted == answer),
+            };
+
+            if !args.json {
+                match (&verdict, &accepted) {
+                    (
+                        Verdict::AlreadySolved {
+                            matches: Some(false),
+                        },
+                        Some(accepted),
+                    ) => println!(
+                        "{} (previously solved as '{}')",
+                        verdict.colorize(answer),
+                        accepted
+                    ),
+                    _ => println!("{} (previously solved)", verdict.colorize(answer)),
+                }
+            }
+
+            incorrect |= verdict.is_incorrect();
+            report.push(serde_json::json!({
+                "part": part,
+                "answer": answer,
+                "result": serde_json::to_value(&verdict)?,
+                "previously_solved": true,
+            }));
+            unaccepted = None;
+            dequeued |= pending.remove(args.year.unwrap(), args.day.unwrap(), part);
+            continue;
+        }
+
+        // the same answer is never submitted twice (it would only cost another cooldown)
+        if let Some(verdict) = history.verdict(part, answer).map(Verdict::without_wait) {
+            if !args.json {
+                println!(
+                    "{} (submitted before: {})",
+                    verdict.colorize(answer),
+                    verdict
+                );
+            }
+
+            incorrect |= verdict.is_incorrect();
+            report.push(serde_json::json!({
+                "part": part,
+                "answer": answer,
+                "result": serde_json::to_value(&verdict)?,
+                "submitted_before": true,
+            }));
+            unaccepted = verdict
+                .is_incorrect()
+                .then_some("part 1 hasn't been accepted");
+            continue;
+        }
+
+        if part == 2
+            && let Some(reason) = unaccepted
+        {
+            if !args.json {
+                println!("{} (not submitted: {})", theme::skipped(answer), reason);
+            }
+
+            report.push(serde_json::json!({
+                "part": part,
+                "answer": answer,
+                "result": { "error": reason },
+            }));
+            continue;
+        }
+
+        // a numeric answer outside of the bounds the server hinted at is wrong for sure
+        if let Some(contradiction) = contradicted_bound(&history, part, answer) {
+            if args.submission.strict {
+                if !args.json {
+                    println!(
+                        "{} (not submitted: {})",
+                        theme::incorrect(answer),
+                        contradiction
+                    );
+                }
+
+                incorrect = true;
+                report.push(serde_json::json!({
+                    "part": part,
+                    "answer": answer,
+                    "result": { "error": contradiction },
+                }));
+                unaccepted = Some("part 1 hasn't been accepted");
+                continue;
+            }
+            warnings::warn(
+                Warning::ContradictedBound,
+                &format!("part {}: {}", part, contradiction),
+            );
+        }
+
+        // whatever the solution printed is submitted, so every answer is confirmed on a terminal
+        // unless the answers were confirmed already
+        if !confirmed
+            && config.confirm_submit
+            && !args.submission.yes
+            && !args.json
+            && !queued
+            && io::stdin().is_terminal()
+            && !confirm(&format!("submit '{}' as part {}?", answer, part))?
+        {
+            println!("{} (not submitted)", theme::skipped(answer));
+            report.push(serde_json::json!({
+                "part": part,
+                "answer": answer,
+                "result": { "error": "declined" },
+            }));
+            unaccepted = Some("part 1 wasn't submitted");
+            continue;
+        }
+
+        // once a submission failed, later parts are queued without being submitted
+        // (part 2 can't be answered before part 1 has been accepted)
+        let submit =
+            || submit::submit_answer(cookie, args.year.unwrap(), args.day.unwrap(), part, answer);
+        let mut result = if queued {
+            Err(anyhow!("previous part could not be submitted"))
+        } else {
+            submit().await
+        };
+
+        // the answer wasn't judged, so it's submitted again once the cooldown is over
+        // (waiting at least a second, so a cooldown which just ran out isn't hammered)
+        while !args.submission.no_retry
+            && let Ok(Verdict::RateLimited { wait }) = &result
+            && let Some(wait) = history::parse_wait(wait)
+        {
+            let message = format!("part {}: submitting '{}' again in", part, answer);
+            countdown::wait(&message, wait.max(chrono::Duration::seconds(1))).await?;
+            result = submit().await;
+        }
+
+        if let Ok(verdict) = &result {
+            history.record(part, answer, verdict);
+            unaccepted = verdict
+                .is_incorrect()
+                .then_some("part 1 hasn't been accepted");
+        }
+
+        // rate limited answers haven't been judged, so they are queued as well
+        let failure = match &result {
+            Ok(verdict @ Verdict::RateLimited { .. }) => Some(verdict.to_string()),
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        };
+
+        match &result {
+            Ok(verdict) if !args.json => {
+                println!("{} ({})", verdict.colorize(answer), verdict)
+            }
+            Err(_) if !args.json => println!("{}", theme::skipped(answer)),
+            _ => {}
+        }
+
+        if failure.is_none() {
+            dequeued |= pending.remove(args.year.unwrap(), args.day.unwrap(), part);
+        }
+        if let Some(reason) = failure {
+            // don't lose the computed answer, keep it for later submission
+            eprintln!(
+                "failed to submit part {}: {} (queued, retry with 'aoc pending submit')",
+                part, reason
+            );
+            pending.push(args.year.unwrap(), args.day.unwrap(), part, answer, &reason);
+            queued = true;
+        }
+
+        incorrect |= result.as_ref().is_ok_and(|v| v.is_incorrect());
+        report.push(serde_json::json!({
+            "part": part,
+            "answer": answer,
+            "result": match &result {
+                Ok(verdict) => serde_json::to_value(verdict)?,
+                Err(e) => serde_json::json!({ "error": e.to_string() }),
+            },
+        }));
+    }
+
+    history.save()?;
+
+    sink::emit(&config.sinks, args.json, &run_result(args, elapsed, report)).await;
+
+    if queued || dequeued {
+        pending.save()?;
+    }
+    if queued {
+        return Ok(EXIT_PARTIAL);
+    }
+
+    if incorrect {
+        return Ok(EXIT_INCORRECT);
+    }
+
+    Ok(EXIT_SUCCESS)
+}
+
+// describes how the answer contradicts the recorded hints of the part (if it does)
+fn contradicted_bound(history: &SubmissionHistory, part: u8, answer: &str) -> Option<String> {
+    let value = answer.trim().parse::<i128>().ok()?;
+
+    match history.bounds(part) {
+        (Some(lower), _) if value <= lower => Some(format!(
+            "'{}' is not higher than '{}' (which was too low)",
+            answer, lower
+        )),
+        (_, Some(upper)) if value >= upper => Some(format!(
+            "'{}' is not lower than '{}' (which was too high)",
+            answer, upper
+        )),
+        _ => None,
+    }
+}
+
+// e.g. "ran in 1.2ms (after 3 warm-up runs)",
+// "took 0.8ms (part 1: 0.3ms, part 2: 0.5ms, reported by the solution)"
+fn timing(elapsed: Duration, reported: Option<&ReportedTime>, warmup: u32) -> String {
+    let mut timing = match reported {
+        Some(reported) => {
+            let mut details: Vec<String> = (1..)
+                .zip(reported.parts)
+                .filter_map(|(part, time)| {
+                    Some(format!("part {}: {}", part, format_duration(time?)))
+                })
+                .collect();
+            details.push("reported by the solution".to_string());
+
+            format!(
+                "took {} ({})",
+                format_duration(reported.total),
+                details.join(", ")
+            )
+        }
+        None => format!("ran in {}", format_duration(elapsed)),
+    };
+    if warmup > 0 {
+        timing.push_str(&format!(" (after {} warm-up runs)", warmup));
+    }
+    timing
+}
+
+// the result of a run or comparison as written to the sinks
+fn run_result(args: &Args, elapsed: Duration, parts: Vec<serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({
+        "mode": format!("{:?}", args.mode).to_lowercase(),
+        "year": args.year.unwrap(),
+        "day": args.day.unwrap(),
+        "language": args.language.map(|language| language.to_string()),
+        "at": clock::now().to_rfc3339(),
+        "time_ms": elapsed.as_secs_f64() * 1000.0,
+        "parts": parts,
+    })
+}
+
+// the text read from the blocks of ascii art in the output (which was shown above), so it can
+// be verified before it's submitted
+fn show_art(stdout: &str) {
+    for (_, text) in ocr::find(stdout) {
+        eprintln!(
+            "{}",
+            theme::skipped(&format!("the ascii art above reads '{}'", text))
+        );
+    }
+}
+
+// the parts of a result which hasn't been submitted (its output was printed instead of the json)
+fn unsubmitted_parts(answers: Option<Answers>) -> Vec<serde_json::Value> {
+    answers
+        .iter()
+        .flat_map(|answers| answers.parts())
+        .filter_map(|(part, answer)| Some(serde_json::json!({ "part": part, "answer": answer? })))
+        .collect()
+}
+
+// the official puzzle input, preferring a cached copy over downloading it again
+async fn official_input(config: &Config, cookie: &str, year: u16, day: u8) -> Result<String> {
+    let cache = Cache::open(&config.cache)?;
+    let key = cache::account_key(cookie, &format!("{}-{}", year, day));
+
+    match cache.get(Resource::Input, &key) {
+        Some(input) => Ok(input),
+        None => {
+            let input = web::get(cookie, &urls::input(year, day)).await?;
+            cache.put(Resource::Input, &key, &input)?;
+            Ok(input)
+        }
+    }
+}
+
+// builds and runs the solution (after the warm-up runs), returning its output and the measured time
+fn execute_solution(
+    config: &Config,
+    args: &Args,
+    input_file: &Path,
+    recording: Option<&Recording>,
+) -> Result<Execution> {
+    // logs are kept for the real project, even if it runs in a copy
+    let real_project_path = config.project_path.clone();
+    // other inputs (e.g. the example of the menu after a failed run) aren't kept as the last output
+    let real_input = input_file == config.input_file(args.year.unwrap(), args.day.unwrap())?;
+
+    // run in a temporary copy of the project (with the input next to it, like in the real layout)
+    // so editor triggered rebuilds or build locks in the real directory can't interfere
+    let isolated = args
+        .execution
+        .isolated
+        .then(|| IsolatedProject::create(config, input_file))
+        .transpose()?;
+    let isolated_config;
+    let config = match &isolated {
+        Some(isolated) => {
+            isolated_config = config.with_project_path(isolated.project_path.clone());
+            &isolated_config
+        }
+        None => config,
+    };
+    // a copy of the project reads the input copied along with it, other inputs (e.g. '--input' or
+    // an example) are read where they are
+    let isolated_input;
+    let input_file = match &isolated {
+        Some(_) if real_input => {
+            isolated_input = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+            &isolated_input
+        }
+        _ => input_file,
+    };
+    let metadata = Metadata::load(&config.project_path)?;
+    let input_arg = metadata.input_arg(config);
+    let input_stdin = metadata.input_stdin(config);
+
+    // an overridden build is up to the project
+    if metadata.build.is_none()
+        && let Some(reason) = args.language.unwrap().debug_build(config)
+    {
+        warnings::warn(
+            Warning::DebugBuild,
+            &format!(
+                "the solution isn't optimized ({}), so its timing is misleading",
+                reason
+            ),
+        );
+    }
+
+    // run build (if exists for given language) command silently (meaning stdout is not printed)
+    let puzzle_env = config.puzzle_env(args.year.unwrap(), args.day.unwrap())?;
+    let mut build_command =
+        metadata
+            .build_command(config, &args.language.unwrap())
+            .map(|mut command| {
+                command.envs(puzzle_env.clone());
+                command
+            });
+
+    // the build is skipped if nothing changed since the last successful one and its output is
+    // still there (a copy of the project is always built)
+    let fingerprint = match (&build_command, &isolated) {
+        (Some(command), None) => Some(freshness::fingerprint(
+            config,
+            &args.language.unwrap(),
+            command,
+        )?),
+        _ => None,
+    };
+    if args.execution.no_build
+        || fingerprint.is_some()
+            && fingerprint == ProjectState::load(&config.project_path)?.last_build
+            && args.language.unwrap().artifact_path(config).is_ok()
+    {
+        build_command = None;
+    }
+
+    let build_start = Instant::now();
+    let build_time = build_command
+        .map(|mut cmd| eval_command_output(&cmd.output()?, true))
+        .transpose()
+        .map_err(SolutionError::Build)?
+        .map(|_| build_start.elapsed());
+    if build_time.is_some()
+        && let Some(fingerprint) = fingerprint
+    {
+        let mut state = ProjectState::load(&config.project_path)?;
+        state.last_build = Some(fingerprint);
+        state.save(&config.project_path)?;
+    }
+
+    let mut run_command = metadata.run_command(config, &args.language.unwrap());
+    run_command.envs(puzzle_env);
+    if args.execution.deterministic {
+        run_command.envs(args.language.unwrap().deterministic_env());
+    }
+    if let Some(threads) = args.execution.threads {
+        run_command.envs(args.language.unwrap().threads_env(threads as usize));
+    }
+    let mut solution_args: Vec<OsString> = match input_arg {
+        true => vec![input_file.into()],
+        false => Vec::new(),
+    };
+    solution_args.extend(args.execution.solution_args.iter().map(OsString::from));
+    if !solution_args.is_empty() {
+        run_command.args(args.language.unwrap().solution_args(config, solution_args));
+    }
+    // lets the solution skip the part which isn't asked for
+    if let Some(part) = args.part {
+        run_command.env("AOC_PART", part.to_string());
+    }
+    // the accepted answer of part 1, for part 2 solutions building upon it
+    if let Some(record) =
+        SubmissionHistory::load(&config.history, args.year.unwrap(), args.day.unwrap())?
+            .get(1)
+            .filter(|record| record.verdict.is_correct() && !record.answer.is_empty())
+    {
+        run_command.env("AOC_PART1_ANSWER", &record.answer);
+    }
+    // the build isn't counted, only the runs of the solution
+    if let Some(recording) = recording {
+        run_command = recording.wrap(&run_command);
+    }
+
+    // the input is also passed as stdin, so huge inputs can be memory mapped (or read from /dev/fd/0)
+    // without relying on the path conventions (AOC_INPUT contains its path)
+    let mut run = |live: bool| -> Result<(Output, Option<u64>)> {
+        // every run gets a fresh file descriptor, so each one starts reading at the beginning
+        if input_file.exists() {
+            run_command.env("AOC_INPUT", input_file);
+            match input_stdin {
+                true => run_command
+                    .env("AOC_INPUT_FD", "0")
+                    .stdin(fs::File::open(input_file)?),
+                // an empty stdin, so a solution reading it anyway doesn't wait for the terminal
+                false => run_command.env_remove("AOC_INPUT_FD").stdin(Stdio::null()),
+            };
+        }
+        process::run(
+            &mut run_command,
+            live,
+            args.execution.timeout.or(config.timeout),
+        )
+    };
+    let ensure_success = |output: &Output, live: bool| match live && !output.status.success() {
+        // the error output has been shown already
+        true => Err(SolutionError::Run(anyhow!("failed: {}", output.status))),
+        false => eval_command_output(output, true).map_err(SolutionError::Run),
+    };
+
+    // warm up runs (e.g. for jit compiled languages) are not measured
+    for _ in 0..args.execution.warmup {
+        ensure_success(&run(false).map_err(SolutionError::Run)?.0, false)?;
+    }
+
+    // the output of the run mode is shown while the solution runs, so a long running one doesn't
+    // seem to hang (json output is kept to the result)
+    let live = args.mode == Mode::Run && !args.json;
+    let start = Instant::now();
+    let (run_output, peak_memory) = run(live).map_err(SolutionError::Run)?;
+    let elapsed = start.elapsed();
+    metrics::record_run(
+        args.year.unwrap(),
+        args.day.unwrap(),
+        &args.language.unwrap().to_string(),
+        run_output.status.success(),
+        elapsed,
+    );
+    if let Some(logs) = &config.logs {
+        logs::keep(logs, &real_project_path, &run_output);
+    }
+    if real_input {
+        outputs::save(args, &run_output)?;
+    }
+    ensure_success(&run_output, live)?;
+
+    Ok(Execution {
+        stdout: String::from_utf8_lossy(&run_output.stdout).to_string(),
+        elapsed,
+        build_time,
+        peak_memory,
+    })
+}
+
+// resolves the project path, inferring the language from the existing projects if none was given
+fn build_project(config: &mut Config, args: &mut Args) -> Result<()> {
+    if args.language.is_none() {
+        let existing: Vec<Language> = if config.uses_parameter("language") {
+            Language::all()
+                .into_iter()
+                .filter(|&language| {
+                    args.language = Some(language);
+                    config.build(args).is_ok() && config.project_path.exists()
+                })
+                .collect()
+        } else {
+            config.build(args)?;
+            Language::all()
+                .into_iter()
+                .filter(|language| language.is_project(&config.project_path))
+                .collect()
+        };
+
+        // ambiguous (or missing) projects still require an explicit language
+        args.language = match existing[..] {
+            [language] => Some(language),
+            _ => None,
+        };
+    }
+
+    config.build(args)?;
+    if args.redo {
+        *config = config.with_project_path(redo_variant(&config.project_path));
+    }
+    Ok(())
+}
+
+// the project of a second attempt at a day, next to the primary one
+fn redo_variant(project_path: &Path) -> PathBuf {
+    match (project_path.parent(), project_path.file_name()) {
+        (Some(parent), Some(name)) => parent.join("redo").join(name),
+        _ => project_path.join("redo"),
+    }
+}
+
+async fn run_day(config: &Config, args: &Args) -> Result<u8> {
+    // throw error if modes working on a project are used without a language
+    if matches!(
+        args.mode,
+        Mode::Run
+            | Mode::Compare
+            | Mode::Perfstat
+            | Mode::Check
+            | Mode::Init
+            | Mode::Path
+            | Mode::Code
+            | Mode::Tag
+            | Mode::Retemplate
+    ) && args.language.is_none()
+    {
+        return Err(anyhow!("language is required for mode '{:?}'", args.mode));
+    }
+
+    // throw error if project doesn't exist for modes that require existence
+    if matches!(
+        args.mode,
+        Mode::Run
+            | Mode::Compare
+            | Mode::Perfstat
+            | Mode::Check
+            | Mode::Code
+            | Mode::Tag
+            | Mode::Retemplate
+    ) && !config.project_path.exists()
+    {
+        return Err(anyhow!(
+            "project does not exist: {}",
+            config.project_path.display()
+        ));
+    }
+
+    if args.mode == Mode::Fetch {
+        config.cookie_for("fetch inputs")?;
+    }
+
+    // check for input file and download if necessary
+    if matches!(
+        args.mode,
+        Mode::Run | Mode::Compare | Mode::Perfstat | Mode::Init | Mode::Fetch | Mode::Input
+    ) {
+        let input_file = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+
+        if !input_file.exists()
+            && let Some(cookie) = &config.cookie
+        {
+            let input =
+                official_input(config, cookie, args.year.unwrap(), args.day.unwrap()).await?;
+
+            if let Some(parent) = input_file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            storage::write(&input_file, input)?;
+        }
+
+        // without a cookie the input has to be put there by hand (unless another one is run)
+        if !input_file.exists()
+            && matches!(args.mode, Mode::Run | Mode::Compare | Mode::Perfstat)
+            && args.input.is_none()
+        {
+            return Err(anyhow!(
+                "the input '{}' doesn't exist (save it there or store your cookie with \
+                 'aoc login' to download it)",
+                input_file.display()
+            ));
+        }
+    }
+
+    match args.mode {
+        Mode::Run => {
+            if args.part == Some(2) && args.day.unwrap() == dates::last_day(args.year.unwrap()) {
+                return Err(anyhow!(
+                    "the last day of {} has no part 2",
+                    args.year.unwrap()
+                ));
+            }
+
+            // the solution runs inside of the project, so a relative path is resolved beforehand
+            let input_file = match &args.input {
+                Some(input) if !input.exists() => {
+                    return Err(anyhow!("the input '{}' doesn't exist", input.display()));
+                }
+                Some(input) => std::path::absolute(input)?,
+                None => config.input_file(args.year.unwrap(), args.day.unwrap())?,
+            };
+
+            // an input is official if it matches the puzzle input downloaded from adventofcode.com
+            // (one which can't be downloaded, e.g. when offline, doesn't keep the solution from running,
+            // its answers just aren't submitted)
+            let mut checked = true;
+            let official = match &config.cookie {
+                Some(cookie) if input_file.exists() => {
+                    match official_input(config, cookie, args.year.unwrap(), args.day.unwrap())
+                        .await
+                    {
+                        Ok(official) => {
+                            Some(fs::read_to_string(&input_file)?.trim_end() == official.trim_end())
+                        }
+                        Err(e) => {
+                            warnings::warn(
+                                Warning::UnusedCookie,
+                                &format!(
+                                    "not submitting: can't check whether '{}' is the official \
+                                     puzzle input ({})",
+                                    input_file.display(),
+                                    e
+                                ),
+                            );
+                            checked = false;
+                            Some(false)
+                        }
+                    }
+                }
+                Some(_) => Some(false),
+                None => None,
+            };
+
+            // the solution might be slow, so tell how long submissions are still blocked before running it
+            if config.show_cooldown && config.cookie.is_some() && !args.json {
+                let history = SubmissionHistory::load(
+                    &config.history,
+                    args.year.unwrap(),
+                    args.day.unwrap(),
+                )?;
+
+                for part in 1..=2 {
+                    if let Some(remaining) = history
+                        .get(part)
+                        .and_then(|record| record.remaining_cooldown(clock::now()))
+                    {
+                        eprintln!(
+                            "{}",
+                            theme::skipped(&format!(
+                                "part {} can't be submitted for another {}",
+                                part,
+                                countdown::format_remaining(remaining)
+                            ))
+                        );
+                    }
+                }
+            }
+
+            let metadata = Metadata::load(&config.project_path)?;
+            let mut state = ProjectState::load(&config.project_path)?;
+
+            // a run on an example is the cheapest way to catch a wrong answer before it's submitted
+            if official == Some(true)
+                && !args.dry_run
+                && state
+                    .last_run
+                    .as_ref()
+                    .is_none_or(|run| run.official != Some(false))
+                && SubmissionHistory::load(&config.history, args.year.unwrap(), args.day.unwrap())?
+                    .submissions
+                    .is_empty()
+            {
+                warnings::warn(
+                    Warning::MissingExample,
+                    "the first answer of the day is about to be submitted, \
+                     but the solution hasn't been run on an example yet",
+                );
+            }
+
+            state.last_run = Some(RunRecord {
+                at: clock::now().to_rfc3339(),
+                input: input_file.display().to_string(),
+                official,
+            });
+            state.save(&config.project_path)?;
+
+            let Execution {
+                stdout,
+                elapsed: measured,
+                build_time,
+                peak_memory,
+            } = execute_solution(config, args, &input_file, None)?;
+
+            // never submit answers computed from an example or otherwise modified input
+            // (a wrong answer would only waste a submission cooldown)
+            let official_run = state
+                .last_run
+                .as_ref()
+                .is_some_and(|run| run.official == Some(true));
+
+            if config.cookie.is_some() && !official_run && checked {
+                warnings::warn(
+                    Warning::UnusedCookie,
+                    &format!(
+                        "not submitting: '{}' does not match the official puzzle input",
+                        input_file.display()
+                    ),
+                );
+            }
+
+            let extractor = metadata.extractor(config)?;
+            let reported = extractor.reported_time(&stdout);
+            let elapsed = reported
+                .as_ref()
+                .map_or(measured, |reported| reported.total);
+
+            // the last day only has a single puzzle (its second star is given for all the others),
+            // so a second line of output is never submitted as part 2
+            let part = match args.day.unwrap() == dates::last_day(args.year.unwrap()) {
+                true => Some(1),
+                false => args.part,
+            };
+
+            // submit answers if cookie is provided and the output follows the parsing strategy
+            let cookie = config.cookie.as_ref().filter(|_| official_run);
+            let answers = || {
+                extractor
+                    .parse(&stdout)
+                    .map(|answers| answers.select(part).normalize(&config.normalize))
+                    .filter(|answers| !answers.is_empty())
+            };
+            // the output has been shown while the solution ran, unless the results are json
+            // (then it goes to stderr, so stdout only holds the json result)
+            let print_output = || {
+                if args.json {
+                    eprintln!("{}", stdout);
+                }
+            };
+            let code = match cookie.map(|cookie| (cookie, answers())) {
+                // everything but the submission itself
+                _ if args.dry_run => {
+                    print_output();
+
+                    match answers() {
+                        Some(answers) => {
+                            for (part, answer) in answers.parts() {
+                                let Some(answer) = answer else {
+                                    continue;
+                                };
+                                eprintln!(
+                                    "{}",
+                                    theme::skipped(&format!(
+                                        "would submit '{}' as part {} to {}",
+                                        answer,
+                                        part,
+                                        urls::answer(args.year.unwrap(), args.day.unwrap())
+                                    ))
+                                );
+                            }
+                        }
+                        None => eprintln!(
+                            "{}",
+                            theme::skipped(&format!(
+                                "nothing would be submitted, the output doesn't follow the '{}' strategy",
+                                extractor
+                            ))
+                        ),
+                    }
+
+                    let parts = unsubmitted_parts(answers());
+                    sink::emit(&config.sinks, args.json, &run_result(args, elapsed, parts)).await;
+                    EXIT_SUCCESS
+                }
+                Some((cookie, Some(answers))) => {
+                    if !args.json {
+                        show_art(&stdout);
+                    }
+                    submit_answers(config, args, cookie, &answers, elapsed, false).await?
+                }
+                Some((cookie, None)) => {
+                    print_output();
+
+                    // the output contained more than the answers, offer the most likely lines for submission
+                    match output::guess_answers(&stdout)
+                        .map(|answers| answers.select(part))
+                        .filter(|answers| !answers.is_empty())
+                    {
+                        Some(answers)
+                            if !args.json
+                                && io::stdin().is_terminal()
+                                && confirm(&format!(
+                                    "the output doesn't follow the '{}' strategy, submit {}?",
+                                    extractor, answers
+                                ))? =>
+                        {
+                            submit_answers(config, args, cookie, &answers, elapsed, true).await?
+                        }
+                        _ => {
+                            let result = run_result(args, elapsed, unsubmitted_parts(None));
+                            sink::emit(&config.sinks, args.json, &result).await;
+                            EXIT_SUCCESS
+                        }
+                    }
+                }
+                None => {
+                    // otherwise just print the output
+                    print_output();
+
+                    let parts = unsubmitted_parts(answers());
+                    sink::emit(&config.sinks, args.json, &run_result(args, elapsed, parts)).await;
+                    EXIT_SUCCESS
+                }
+            };
+
+            if !args.json {
+                // tells whether a slow turnaround is down to the compiler or the solution (the
+                // wall clock time of the run is only left out if it's shown below anyway)
+                if let Some(build_time) = build_time {
+                    let built = format!("built in {}", format_duration(build_time));
+                    eprintln!(
+                        "{}",
+                        theme::timing(&match reported {
+                            Some(_) => format!(
+                                "{}, ran in {} (wall clock)",
+                                built,
+                                format_duration(measured)
+                            ),
+                            None => built,
+                        })
+                    );
+                }
+                eprintln!(
+                    "{}",
+                    theme::timing(&timing(elapsed, reported.as_ref(), args.execution.warmup))
+                );
+                if let Some(peak_memory) = peak_memory {
+                    eprintln!(
+                        "{}",
+                        theme::timing(&format!(
+                            "peak memory: {}",
+                            process::format_memory(peak_memory)
+                        ))
+                    );
+                }
+            }
+
+            return Ok(code);
+        }
+        // an answer computed outside of a solution (e.g. by hand), judged like the ones of run mode
+        Mode::Submit => {
+            let last_day = args.day.unwrap() == dates::last_day(args.year.unwrap());
+            if args.part == Some(2) && last_day {
+                return Err(anyhow!(
+                    "the last day of {} has no part 2",
+                    args.year.unwrap()
+                ));
+            }
+            let cookie = config.cookie_for("submit answers")?;
+
+            let answers = match (&args.from_file, &args.params[..]) {
+                // the first line is the answer of part 1 and the second one the answer of part 2
+                // (either can be left empty, e.g. for files written by solutions)
+                (Some(path), []) => {
+                    let content = fs::read_to_string(path)
+                        .map_err(|e| anyhow!("failed to read '{}': {}", path.display(), e))?;
+                    let lines: Vec<&str> = content.trim_end().lines().map(str::trim).collect();
+                    if lines.len() > 2 {
+                        return Err(anyhow!("'{}' contains more than two lines", path.display()));
+                    }
+
+                    let answer = |i: usize| {
+                        lines
+                            .get(i)
+                            .filter(|line| !line.is_empty())
+                            .map(|line| line.to_string())
+                    };
+                    let part = if last_day { Some(1) } else { args.part };
+                    let answers = Answers {
+                        part1: answer(0),
+                        part2: answer(1),
+                    }
+                    .select(part);
+                    if answers.is_empty() {
+                        return Err(anyhow!("'{}' contains no answer to submit", path.display()));
+                    }
+                    answers
+                }
+                (None, [answer]) => {
+                    let part = args.part.ok_or(anyhow!(
+                        "submit mode requires the part of the answer (--part)"
+                    ))?;
+                    let answer = Some(answer.trim().to_string());
+                    Answers {
+                        part1: answer.clone(),
+                        part2: answer,
+                    }
+                    .select(Some(part))
+                }
+                (Some(_), _) => {
+                    return Err(anyhow!("submit mode takes either an answer or --from-file"));
+                }
+                (None, _) => {
+                    return Err(anyhow!(
+                        "submit mode requires a single answer (or --from-file)"
+                    ));
+                }
+            };
+            return submit_answers(config, args, cookie, &answers, Duration::ZERO, true).await;
+        }
+        Mode::Compare => {
+            let against = args
+                .against
+                .as_ref()
+                .ok_or(anyhow!("compare mode requires --against <dir-or-url>"))?;
+            let theirs = Manifest::load(against)
+                .await?
+                .get(args.year.unwrap(), args.day.unwrap())
+                .ok_or(anyhow!(
+                    "'{}' has no answers for {}/{:0>2}",
+                    against,
+                    args.year.unwrap(),
+                    args.day.unwrap()
+                ))?;
+
+            let input_file = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+            let Execution {
+                stdout,
+                elapsed: measured,
+                ..
+            } = execute_solution(config, args, &input_file, None)?;
+
+            let extractor = Metadata::load(&config.project_path)?.extractor(config)?;
+            let reported = extractor.reported_time(&stdout);
+            let elapsed = reported
+                .as_ref()
+                .map_or(measured, |reported| reported.total);
+            let answers = extractor.parse(&stdout).ok_or(anyhow!(
+                "the output doesn't follow the '{}' strategy:\n{}",
+                extractor,
+                stdout.trim_end()
+            ))?;
+
+            let mut disagrees = false;
+            let mut report = Vec::new();
+
+            for ((part, answer), (_, their_answer)) in
+                answers.parts().into_iter().zip(theirs.parts())
+            {
+                let agrees = answer
+                    .zip(their_answer)
+                    .map(|(answer, theirs)| answer == theirs);
+                disagrees |= agrees == Some(false);
+
+                if args.json {
+                    report.push(serde_json::json!({
+                        "part": part,
+                        "answer": answer,
+                        "theirs": their_answer,
+                        "agrees": agrees,
+                    }));
+                    continue;
+                }
+
+                match (answer, their_answer) {
+                    (Some(answer), Some(_)) if agrees == Some(true) => {
+                        println!("part {}: {} (agrees)", part, theme::correct(answer))
+                    }
+                    (Some(answer), Some(theirs)) => {
+                        println!(
+                            "part {}: {} (theirs is {})",
+                            part,
+                            theme::incorrect(answer),
+                            theirs
+                        )
+                    }
+                    (Some(answer), None) => {
+                        println!(
+                            "part {}: {} (nothing to compare against)",
+                            part,
+                            theme::skipped(answer)
+                        )
+                    }
+                    (None, Some(theirs)) => {
+                        println!("part {}: no answer (theirs is {})", part, theirs)
+                    }
+                    (None, None) => {}
+                }
+            }
+
+            sink::emit(&config.sinks, args.json, &run_result(args, elapsed, report)).await;
+            if !args.json {
+                eprintln!("{}", theme::timing(&timing(elapsed, reported.as_ref(), 0)));
+            }
+
+            return Ok(if disagrees {
+                EXIT_INCORRECT
+            } else {
+                EXIT_SUCCESS
+            });
+        }
+        Mode::Perfstat => {
+            let recording = Recording::new()?;
+            let input_file = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+            let Execution {
+                stdout,
+                elapsed: measured,
+                ..
+            } = execute_solution(config, args, &input_file, Some(&recording))?;
+            let counters = recording.counters()?;
+
+            let extractor = Metadata::load(&config.project_path)?.extractor(config)?;
+            let reported = extractor.reported_time(&stdout);
+            let elapsed = reported
+                .as_ref()
+                .map_or(measured, |reported| reported.total);
+
+            // nothing is submitted, the counters are recorded along with the answers
+            let mut result = run_result(args, elapsed, unsubmitted_parts(extractor.parse(&stdout)));
+            result["counters"] = serde_json::to_value(&counters)?;
+
+            if !args.json {
+                println!("{}", stdout);
+            }
+            sink::emit(&config.sinks, args.json, &result).await;
+            if !args.json {
+                eprintln!(
+                    "{}",
+                    theme::timing(&timing(elapsed, reported.as_ref(), args.execution.warmup))
+                );
+                eprintln!("{}", theme::timing(&counters.to_string()));
+            }
+
+            return Ok(EXIT_SUCCESS);
+        }
+        Mode::Check => {
+            return Ok(match check::run(config, args)? {
+                true => EXIT_SUCCESS,
+                false => EXIT_FAILURE,
+            });
+        }
+        Mode::Init => {
+            let input_file = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+
+            // every selected language is scaffolded at the same time
+            let languages = match args.languages.len() {
+                0 => vec![args.language.unwrap()],
+                _ => args.languages.clone(),
+            };
+            let mut projects = languages
+                .into_iter()
+                .map(|language| {
+                    let project_path = config.resolve(args.year, args.day, Some(language))?;
+                    let project_path = match args.redo {
+                        true => redo_variant(&project_path),
+                        false => project_path,
+                    };
+                    Ok((language, config.with_project_path(project_path)))
+                })
+                .collect::<Result<Vec<(Language, Config)>>>()?;
+
+            // initializing a solved day again usually happens while reorganizing the solutions,
+            // or to try another approach, which is what the redo variant is for
+            if !args.redo {
+                let (year, day) = (args.year.unwrap(), args.day.unwrap());
+                let history = SubmissionHistory::load(&config.history, year, day)?;
+                let solved: Vec<_> = (1..=2)
+                    .filter_map(|part| history.get(part))
+                    .filter(|record| record.verdict.is_correct())
+                    .collect();
+                let stars = day_stars(config, year, day).await?;
+
+                if stars > 0 {
+                    eprintln!(
+                        "{}",
+                        theme::skipped(&format!(
+                            "{}/{:0>2} is already solved ({} {})",
+                            year,
+                            day,
+                            stars,
+                            if stars == 1 { "star" } else { "stars" }
+                        ))
+                    );
+                    for record in &solved {
+                        match record.answer.as_str() {
+                            "" => eprintln!(
+                                "part {}: solved (the answer wasn't recorded)",
+                                record.part
+                            ),
+                            answer => eprintln!("part {}: {}", record.part, theme::correct(answer)),
+                        }
+                    }
+
+                    let redo = redo_variant(&projects[0].1.project_path);
+                    if !args.json
+                        && io::stdin().is_terminal()
+                        && confirm(&format!(
+                            "initialize the redo variant in '{}' instead?",
+                            redo.display()
+                        ))?
+                    {
+                        projects = projects
+                            .into_iter()
+                            .map(|(language, config)| {
+                                let project_path = redo_variant(&config.project_path);
+                                (language, config.with_project_path(project_path))
+                            })
+                            .collect();
+                    }
+                }
+            }
+
+            // fail before any project is created if a language has no harness
+            if args.with_harness {
+                for (language, config) in &projects {
+                    harness::files(language, config)?;
+                }
+            }
+
+            // throw error if trying to initialize but a project already exists, before any of the
+            // projects is created (an input downloaded into the project directory doesn't count)
+            for (_, config) in &projects {
+                let project_input = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+                let existing = fs::read_dir(&config.project_path).is_ok_and(|entries| {
+                    entries.flatten().any(|entry| entry.path() != project_input)
+                });
+                if existing {
+                    return Err(anyhow!(
+                        "project already exists: {}",
+                        config.project_path.display()
+                    ));
+                }
+            }
+
+            let mut running = Vec::new();
+
+            for (language, config) in &projects {
+                let project_input = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+
+                fs::create_dir_all(&config.project_path)
+                    .map_err(|e| anyhow!("failed to create project directory: {}", e))?;
+
+                let style = EditorConfig::load(&config.project_path)?;
+                for (name, content) in language.init_files(config) {
+                    let path = config.project_path.join(name);
+                    fs::write(&path, style.format(&path, content.as_bytes()))?;
+                }
+
+                // the input has only been downloaded once, the other projects share it
+                if project_input != input_file && !project_input.exists() && input_file.exists() {
+                    fs::copy(&input_file, &project_input)?;
+                }
+
+                running.push(
+                    language
+                        .init_command(config)
+                        .envs(config.puzzle_env(args.year.unwrap(), args.day.unwrap())?)
+                        .stdin(Stdio::null())
+                        .stdout(Stdio::piped())
+                        .stderr(Stdio::piped())
+                        .spawn()?,
+                );
+            }
+
+            for ((language, config), child) in projects.iter().zip(running) {
+                eval_command_output(&child.wait_with_output()?, false)?;
+
+                // make the shared helpers available right away
+                if let Some(mut link_command) = language.link_common_command(config) {
+                    eval_command_output(&link_command.output()?, true)?;
+                }
+
+                // the harness replaces the generated entry point and speaks the json protocol
+                if args.with_harness {
+                    let style = EditorConfig::load(&config.project_path)?;
+                    for (name, content) in harness::files(language, config)? {
+                        let path = config.project_path.join(name);
+                        fs::write(&path, style.format(&path, content.as_bytes()))?;
+                    }
+
+                    let mut metadata = Metadata::load(&config.project_path)?;
+                    metadata.output = Some(OutputStrategy::Json);
+                    metadata.save(&config.project_path)?;
+                }
+
+                // the boilerplate of the user replaces the generated files
+                if let Some(template) = config.scaffold_path(language) {
+                    let mut metadata = Metadata::load(&config.project_path)?;
+                    let style = EditorConfig::load(&config.project_path)?;
+                    let placeholders = scaffold_placeholders(
+                        config,
+                        args.year.unwrap(),
+                        args.day.unwrap(),
+                        *language,
+                    )
+                    .await?;
+                    metadata.scaffold =
+                        scaffold::apply(&template, &config.project_path, &placeholders, &style)?;
+                    metadata.save(&config.project_path)?;
+                }
+
+                // the build keeps running after the tool exits, its output is of no interest
+                if config.prebuild
+                    && let Some(mut build) =
+                        Metadata::load(&config.project_path)?.build_command(config, language)
+                {
+                    build
+                        .envs(config.puzzle_env(args.year.unwrap(), args.day.unwrap())?)
+                        .stdin(Stdio::null())
+                        .stdout(Stdio::null())
+                        .stderr(Stdio::null())
+                        .spawn()?;
+                }
+            }
+        }
+        Mode::Path => match args.artifact {
+            true => println!(
+                "{}",
+                args.language.unwrap().artifact_path(config)?.display()
+            ),
+            false => println!("{}", config.project_path.display()),
+        },
+        Mode::Code => {
+            command!("code", &config.project_path).spawn()?;
+        }
+        Mode::Url => {
+            println!("{}", urls::puzzle(args.year.unwrap(), args.day.unwrap()));
+        }
+        Mode::Fetch => {
+            println!(
+                "{}",
+                config
+                    .input_file(args.year.unwrap(), args.day.unwrap())?
+                    .display()
+            );
+        }
+        Mode::Input => {
+            let input_file = config.input_file(args.year.unwrap(), args.day.unwrap())?;
+            let input = fs::read_to_string(&input_file)
+                .map_err(|e| anyhow!("failed to read '{}': {}", input_file.display(), e))?;
+
+            match &args.scramble {
+                // a structurally similar input which can be shared without leaking the real one
+                Some(path) => {
+                    storage::write(
+                        path,
+                        scramble::scramble(&input, &args.scramble_strategies, args.seed),
+                    )
+                    .map_err(|e| anyhow!("failed to write '{}': {}", path.display(), e))?;
+                    println!("{}", path.display());
+                }
+                None => print!("{}", input),
+            }
+        }
+        Mode::Tag => {
+            let mut metadata = Metadata::load(&config.project_path)?;
+
+            for tag in &args.params {
+                let tag = tag.to_lowercase();
+                if args.remove {
+                    metadata.tags.retain(|t| *t != tag);
+                } else if !metadata.tags.contains(&tag) {
+                    metadata.tags.push(tag);
+                }
+            }
+
+            if !args.params.is_empty() {
+                metadata.tags.sort();
+                metadata.save(&config.project_path)?;
+            }
+
+            println!("{}", metadata.tags.join(", "));
+        }
+        Mode::Retemplate => {
+            let language = args.language.unwrap();
+            let template = config
+                .scaffold_path(&language)
+                .ok_or(anyhow!("no scaffold template configured for {}", language))?;
+
+            // only files which haven't been edited since they were scaffolded are updated
+            let mut metadata = Metadata::load(&config.project_path)?;
+            let style = EditorConfig::load(&config.project_path)?;
+            let placeholders =
+                scaffold_placeholders(config, args.year.unwrap(), args.day.unwrap(), language)
+                    .await?;
+            let reapplied = scaffold::reapply(
+                &template,
+                &config.project_path,
+                &mut metadata.scaffold,
+                &placeholders,
+                &style,
+            )?;
+            metadata.save(&config.project_path)?;
+
+            for file in &reapplied.updated {
+                println!("{} {}", theme::correct("updated"), file);
+            }
+            for file in &reapplied.modified {
+                println!(
+                    "{} {} (modified since it was scaffolded)",
+                    theme::skipped("skipped"),
+                    file
+                );
+            }
+        }
+        Mode::Countdown
+        | Mode::Art
+        | Mode::List
+        | Mode::Vendor
+        | Mode::Matrix
+        | Mode::Stats
+        | Mode::Metrics
+        | Mode::Answers
+        | Mode::ImportHistory
+        | Mode::Cache
+        | Mode::Pending
+        | Mode::Login => unreachable!(),
+    }
+
+    Ok(EXIT_SUCCESS)
 }
@@ -0,0 +1,66 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{output::Answers, web};
+
+// file looked up when a directory is given instead of a manifest file
+const MANIFEST_FILE: &str = "answers.yaml";
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ManifestEntry {
+    part1: Option<serde_yml::Value>,
+    part2: Option<serde_yml::Value>,
+}
+
+// answers published by someone else (without their inputs), e.g.
+//
+// 2024:
+//   1: { part1: 1234, part2: 5678 }
+//   2: { part1: 42 }
+#[derive(Deserialize, Default)]
+pub struct Manifest(BTreeMap<u16, BTreeMap<u8, ManifestEntry>>);
+
+impl Manifest {
+    // loads the manifest from a url, a file or a directory containing "answers.yaml"
+    // (json works as well)
+    pub async fn load(against: &str) -> Result<Self> {
+        let content = if against.starts_with("http://") || against.starts_with("https://") {
+            web::throttled(reqwest::get(against))
+                .await?
+                .error_for_status()?
+                .text()
+                .await
+                .with_context(|| format!("failed to download manifest '{}'", against))?
+        } else {
+            let path = Path::new(against);
+            let path = if path.is_dir() {
+                path.join(MANIFEST_FILE)
+            } else {
+                path.to_path_buf()
+            };
+
+            fs::read_to_string(&path)
+                .with_context(|| format!("failed to read manifest '{}'", path.display()))?
+        };
+
+        serde_yml::from_str(&content)
+            .map_err(|e| anyhow!("failed to parse manifest '{}': {}", against, e))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<Answers> {
+        // answers can be written as strings or numbers
+        let value_to_string = |value: &serde_yml::Value| match value {
+            serde_yml::Value::String(s) => Some(s.clone()),
+            serde_yml::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        };
+
+        let entry = self.0.get(&year)?.get(&day)?;
+        Some(Answers {
+            part1: entry.part1.as_ref().and_then(value_to_string),
+            part2: entry.part2.as_ref().and_then(value_to_string),
+        })
+    }
+}
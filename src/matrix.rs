@@ -0,0 +1,230 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{fmt::Write, time::Duration};
+
+use crate::{format_duration, theme};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MatrixFormat {
+    // aligned columns for the terminal
+    Table,
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    // every answer matches the accepted one
+    Passed,
+    // an answer differs from the accepted one (or is missing)
+    Failed,
+    // answers without accepted ones to compare them against
+    Unverified,
+    BuildFailed,
+    RunFailed,
+    TimedOut,
+    // the day has no project in the language
+    Missing,
+    // the input couldn't be found (or downloaded)
+    NoInput,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Passed => "ok",
+            Status::Failed => "wrong",
+            Status::Unverified => "unverified",
+            Status::BuildFailed => "build failed",
+            Status::RunFailed => "failed",
+            Status::TimedOut => "timed out",
+            Status::Missing => "-",
+            Status::NoInput => "no input",
+        }
+    }
+}
+
+// an answer of a cell along with the accepted one
+#[derive(Serialize)]
+pub struct Part {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub accepted: Option<String>,
+}
+
+// the result of a single project (a day in a language)
+#[derive(Serialize)]
+pub struct Cell {
+    pub year: u16,
+    pub day: u8,
+    pub language: String,
+    pub status: Status,
+    #[serde(skip)]
+    pub time: Option<Duration>,
+    pub time_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<Part>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Cell {
+    pub fn new(year: u16, day: u8, language: String, status: Status) -> Self {
+        Self {
+            year,
+            day,
+            language,
+            status,
+            time: None,
+            time_ms: None,
+            parts: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn with_time(mut self, time: Duration) -> Self {
+        self.time = Some(time);
+        self.time_ms = Some(time.as_secs_f64() * 1000.0);
+        self
+    }
+
+    // e.g. "ok 1.20ms", "wrong 3.45s" or "build failed"
+    pub fn summary(&self) -> String {
+        match self.time {
+            Some(time) => format!("{} {}", self.status.label(), format_duration(time)),
+            None => self.status.label().to_string(),
+        }
+    }
+
+    fn colorize(&self, text: &str) -> String {
+        match self.status {
+            Status::Passed => theme::correct(text).to_string(),
+            Status::Unverified | Status::Missing | Status::NoInput => {
+                theme::skipped(text).to_string()
+            }
+            _ => theme::incorrect(text).to_string(),
+        }
+    }
+}
+
+// e.g. "24/25 in 3.21s" (the time of the passed days)
+fn total(cells: &[&Cell]) -> String {
+    let run: Vec<&&Cell> = cells
+        .iter()
+        .filter(|cell| cell.status != Status::Missing)
+        .collect();
+    let passed: Vec<&&Cell> = run
+        .iter()
+        .copied()
+        .filter(|cell| cell.status == Status::Passed)
+        .collect();
+    let time: Duration = passed.iter().filter_map(|cell| cell.time).sum();
+
+    match passed.is_empty() {
+        true => format!("0/{}", run.len()),
+        false => format!(
+            "{}/{} in {}",
+            passed.len(),
+            run.len(),
+            format_duration(time)
+        ),
+    }
+}
+
+// the cells as a grid of the days (rows) and languages (columns), with a row of totals per language
+pub fn render(cells: &[Cell], languages: &[String], format: MatrixFormat) -> String {
+    if format == MatrixFormat::Json {
+        return serde_json::to_string_pretty(cells).unwrap() + "\n";
+    }
+
+    let mut days: Vec<(u16, u8)> = cells.iter().map(|cell| (cell.year, cell.day)).collect();
+    days.dedup();
+
+    let cell = |year: u16, day: u8, language: &str| {
+        cells
+            .iter()
+            .find(|cell| cell.year == year && cell.day == day && cell.language == language)
+    };
+    let totals: Vec<String> = languages
+        .iter()
+        .map(|language| {
+            total(
+                &cells
+                    .iter()
+                    .filter(|cell| &cell.language == language)
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+
+    let mut rendered = String::new();
+    match format {
+        MatrixFormat::Markdown => {
+            let _ = writeln!(rendered, "| day | {} |", languages.join(" | "));
+            let _ = writeln!(rendered, "|---|{}", "---|".repeat(languages.len()));
+            for &(year, day) in &days {
+                let row: Vec<String> = languages
+                    .iter()
+                    .map(|language| {
+                        cell(year, day, language).map_or("-".to_string(), Cell::summary)
+                    })
+                    .collect();
+                let _ = writeln!(rendered, "| {}/{:0>2} | {} |", year, day, row.join(" | "));
+            }
+            let _ = writeln!(rendered, "| **total** | {} |", totals.join(" | "));
+        }
+        _ => {
+            // the widths are computed before the colors are added
+            let widths: Vec<usize> = languages
+                .iter()
+                .zip(&totals)
+                .map(|(language, total)| {
+                    days.iter()
+                        .filter_map(|&(year, day)| cell(year, day, language))
+                        .map(|cell| cell.summary().chars().count())
+                        .chain([language.len(), total.chars().count()])
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+
+            let header: Vec<String> = languages
+                .iter()
+                .zip(&widths)
+                .map(|(language, width)| format!("{:<width$}", language, width = width))
+                .collect();
+            let _ = writeln!(rendered, "{:<7}  {}", "", header.join("  ").trim_end());
+
+            for &(year, day) in &days {
+                let row: Vec<String> = languages
+                    .iter()
+                    .zip(&widths)
+                    .map(|(language, width)| match cell(year, day, language) {
+                        Some(cell) => {
+                            let summary = format!("{:<width$}", cell.summary(), width = width);
+                            cell.colorize(&summary)
+                        }
+                        None => format!("{:<width$}", "-", width = width),
+                    })
+                    .collect();
+                let _ = writeln!(
+                    rendered,
+                    "{}/{:0>2}  {}",
+                    year,
+                    day,
+                    row.join("  ").trim_end()
+                );
+            }
+
+            let totals: Vec<String> = totals
+                .iter()
+                .zip(&widths)
+                .map(|(total, width)| format!("{:<width$}", total, width = width))
+                .collect();
+            let _ = writeln!(rendered, "{:<7}  {}", "total", totals.join("  ").trim_end());
+        }
+    }
+
+    rendered
+}
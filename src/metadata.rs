@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    args::Language,
+    config::Config,
+    custom,
+    output::{Extractor, OutputStrategy},
+    paths, storage,
+};
+
+pub const METADATA_FILE: &str = "aoc.yaml";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunRecord {
+    pub at: String,
+    pub input: String,
+    // whether the input matched the official puzzle input (unknown without a session)
+    pub official: Option<bool>,
+}
+
+// per-project metadata stored next to the solution
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Metadata {
+    // overrides the output parsing strategy of the config for this project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputStrategy>,
+    // regex patterns extracting the answers (one per part), replace the output strategy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub answer_patterns: Vec<String>,
+    // overrides whether the input path is passed as the first argument for this project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_arg: Option<bool>,
+    // overrides whether the input is piped into the stdin of the solution for this project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_stdin: Option<bool>,
+    // commands replacing the build and run commands of the language for this project (e.g.
+    // "./run.sh"), run by the shell inside of it with the placeholders of custom languages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    // user defined tags (e.g. "graph", "regex", "hard")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // hashes of the files copied from the scaffold template (to detect untouched files)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub scaffold: BTreeMap<String, String>,
+}
+
+// what's remembered about the runs of a project, kept outside of it (see paths::project_state_dir)
+// so that the project isn't changed by running it
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ProjectState {
+    pub last_run: Option<RunRecord>,
+    // the fingerprint of the sources of the last successful build (see freshness)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_build: Option<String>,
+}
+
+impl ProjectState {
+    fn path(project_path: &Path) -> Result<PathBuf> {
+        Ok(paths::project_state_dir(project_path)?.join("state.yaml"))
+    }
+
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let path = Self::path(project_path)?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read project state '{}'", path.display()))?;
+        serde_yml::from_str(&content)
+            .with_context(|| format!("failed to parse project state '{}'", path.display()))
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<()> {
+        let path = Self::path(project_path)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        storage::write(&path, serde_yml::to_string(self)?)
+            .with_context(|| format!("failed to write project state '{}'", path.display()))
+    }
+}
+
+impl Metadata {
+    pub fn load(project_path: &Path) -> Result<Self> {
+        let path = project_path.join(METADATA_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read metadata '{}'", path.display()))?;
+        serde_yml::from_str(&content)
+            .with_context(|| format!("failed to parse metadata '{}'", path.display()))
+    }
+
+    // the configured answer extraction, the settings of the project take precedence over the config
+    pub fn extractor(&self, config: &Config) -> Result<Extractor> {
+        match (&self.answer_patterns[..], self.output) {
+            ([], Some(strategy)) => Extractor::new(strategy, &[]),
+            ([], None) => Extractor::new(config.output, &config.answer_patterns()),
+            (patterns, strategy) => Extractor::new(strategy.unwrap_or(config.output), patterns),
+        }
+    }
+
+    pub fn input_arg(&self, config: &Config) -> bool {
+        self.input_arg.unwrap_or(config.input_arg)
+    }
+
+    pub fn input_stdin(&self, config: &Config) -> bool {
+        self.input_stdin.unwrap_or(config.input_stdin)
+    }
+
+    // the build command of the project, the one of the language unless it's overridden
+    pub fn build_command(&self, config: &Config, language: &Language) -> Option<Command> {
+        match &self.build {
+            Some(build) => Some(Metadata::command(build, config, language)),
+            None => language.build_command(config),
+        }
+    }
+
+    pub fn run_command(&self, config: &Config, language: &Language) -> Command {
+        match &self.run {
+            Some(run) => Metadata::command(run, config, language),
+            None => language.run_command(config),
+        }
+    }
+
+    fn command(template: &str, config: &Config, language: &Language) -> Command {
+        let mut command = custom::command(template, config, language);
+        command.envs(config.language_env(language));
+        command
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<()> {
+        let path = project_path.join(METADATA_FILE);
+
+        storage::write(&path, serde_yml::to_string(self)?)
+            .with_context(|| format!("failed to write metadata '{}'", path.display()))
+    }
+}
@@ -0,0 +1,289 @@
+use anyhow::{Context, Result, anyhow};
+use chrono::DateTime;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap, fmt::Write, fs, net::SocketAddr, path::PathBuf, sync::Mutex,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use crate::{config::Config, dates, history::SubmissionHistory, paths, storage, theme};
+
+// the number and the total duration of observations (a prometheus summary without quantiles)
+#[derive(Serialize, Deserialize, Clone)]
+struct Observed<T> {
+    #[serde(flatten)]
+    labels: T,
+    count: u64,
+    seconds: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct RunLabels {
+    year: u16,
+    day: u8,
+    language: String,
+    success: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct RequestLabels {
+    // the path with the year and day left out, e.g. "/{year}/day/{day}/input"
+    endpoint: String,
+    // the status code of the response or "error" if there was none
+    status: String,
+}
+
+// the runs of solutions and the requests to adventofcode.com so far, kept in the data directory
+// (the submissions are taken from the history instead)
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Recorded {
+    runs: Vec<Observed<RunLabels>>,
+    requests: Vec<Observed<RequestLabels>>,
+}
+
+impl Recorded {
+    fn path() -> Result<PathBuf> {
+        Ok(paths::data_dir()?.join("metrics.yaml"))
+    }
+
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read metrics '{}'", path.display()))?;
+        serde_yml::from_str(&content)
+            .with_context(|| format!("failed to parse metrics '{}'", path.display()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        storage::write(&path, serde_yml::to_string(self)?)
+            .with_context(|| format!("failed to write metrics '{}'", path.display()))
+    }
+}
+
+fn observe<T: PartialEq>(series: &mut Vec<Observed<T>>, labels: T, elapsed: Duration) {
+    match series.iter_mut().find(|observed| observed.labels == labels) {
+        Some(observed) => {
+            observed.count += 1;
+            observed.seconds += elapsed.as_secs_f64();
+        }
+        None => series.push(Observed {
+            labels,
+            count: 1,
+            seconds: elapsed.as_secs_f64(),
+        }),
+    }
+}
+
+// concurrent requests of the process are recorded one after another, so none of them gets lost
+static RECORDING: Mutex<()> = Mutex::new(());
+
+// a metric which can't be recorded never fails what's measured, so it's only reported
+fn record(update: impl FnOnce(&mut Recorded)) {
+    let _guard = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+
+    let recorded = Recorded::load().and_then(|mut recorded| {
+        update(&mut recorded);
+        recorded.save()
+    });
+    if let Err(e) = recorded {
+        eprintln!(
+            "{}",
+            theme::skipped(&format!("failed to record metrics: {}", e))
+        );
+    }
+}
+
+pub fn record_run(year: u16, day: u8, language: &str, success: bool, elapsed: Duration) {
+    let labels = RunLabels {
+        year,
+        day,
+        language: language.to_string(),
+        success,
+    };
+    record(|recorded| observe(&mut recorded.runs, labels, elapsed));
+}
+
+pub fn record_request(path: &str, status: &str, elapsed: Duration) {
+    let year = Regex::new(r"^/\d{4}").unwrap();
+    let day = Regex::new(r"/day/\d+").unwrap();
+    let labels = RequestLabels {
+        endpoint: day
+            .replace(&year.replace(path, "/{year}"), "/day/{day}")
+            .to_string(),
+        status: status.to_string(),
+    };
+    record(|recorded| observe(&mut recorded.requests, labels, elapsed));
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// the metrics of the given years in the prometheus text format, e.g. to be scraped from
+// "aoc metrics --listen" or picked up by the textfile collector of the node exporter
+pub fn render(config: &Config, years: &[u16]) -> Result<String> {
+    let mut verdicts = String::new();
+    let mut submissions = BTreeMap::new();
+    let mut solve_times = String::new();
+
+    for &year in years {
+        for day in 1..=dates::last_day(year) {
+            let history = SubmissionHistory::load(&config.history, year, day)?;
+
+            for record in &history.submissions {
+                let labels = format!(
+                    "year=\"{}\",day=\"{}\",part=\"{}\",by=\"{}\"",
+                    year,
+                    day,
+                    record.part,
+                    escape(&record.by)
+                );
+
+                writeln!(
+                    verdicts,
+                    "aoc_submission_verdict{{{},verdict=\"{}\"}} 1",
+                    labels,
+                    record.verdict.label()
+                )?;
+
+                for verdict in std::iter::once(&record.verdict)
+                    .chain(record.earlier.iter().map(|judged| &judged.verdict))
+                {
+                    *submissions.entry((year, verdict.label())).or_insert(0) += 1;
+                }
+
+                if record.verdict.is_correct()
+                    && let Ok(submitted_at) = DateTime::parse_from_rfc3339(&record.submitted_at)
+                {
+                    let seconds = (submitted_at - dates::unlock(year, day)).num_seconds();
+                    writeln!(solve_times, "aoc_solve_seconds{{{}}} {}", labels, seconds)?;
+                }
+            }
+        }
+    }
+
+    let mut submission_counts = String::new();
+    for ((year, verdict), count) in submissions {
+        writeln!(
+            submission_counts,
+            "aoc_submissions_total{{year=\"{}\",verdict=\"{}\"}} {}",
+            year, verdict, count
+        )?;
+    }
+
+    let recorded = Recorded::load()?;
+    let mut runs = String::new();
+    for run in recorded
+        .runs
+        .iter()
+        .filter(|run| years.contains(&run.labels.year))
+    {
+        let labels = format!(
+            "year=\"{}\",day=\"{}\",language=\"{}\",success=\"{}\"",
+            run.labels.year,
+            run.labels.day,
+            escape(&run.labels.language),
+            run.labels.success
+        );
+        writeln!(runs, "aoc_run_seconds_sum{{{}}} {}", labels, run.seconds)?;
+        writeln!(runs, "aoc_run_seconds_count{{{}}} {}", labels, run.count)?;
+    }
+    let mut requests = String::new();
+    for request in &recorded.requests {
+        let labels = format!(
+            "endpoint=\"{}\",status=\"{}\"",
+            escape(&request.labels.endpoint),
+            escape(&request.labels.status)
+        );
+        writeln!(
+            requests,
+            "aoc_api_request_seconds_sum{{{}}} {}",
+            labels, request.seconds
+        )?;
+        writeln!(
+            requests,
+            "aoc_api_request_seconds_count{{{}}} {}",
+            labels, request.count
+        )?;
+    }
+
+    Ok(format!(
+        concat!(
+            "# HELP aoc_submission_verdict The verdict of the last submission per part.\n",
+            "# TYPE aoc_submission_verdict gauge\n",
+            "{}",
+            "# HELP aoc_submissions_total Judged submissions per verdict.\n",
+            "# TYPE aoc_submissions_total counter\n",
+            "{}",
+            "# HELP aoc_solve_seconds Seconds from the unlock of the puzzle to the correct answer.\n",
+            "# TYPE aoc_solve_seconds gauge\n",
+            "{}",
+            "# HELP aoc_run_seconds Duration of the runs of the solutions (counting the runs).\n",
+            "# TYPE aoc_run_seconds summary\n",
+            "{}",
+            "# HELP aoc_api_request_seconds Latency of the requests to adventofcode.com.\n",
+            "# TYPE aoc_api_request_seconds summary\n",
+            "{}"
+        ),
+        verdicts, submission_counts, solve_times, runs, requests
+    ))
+}
+
+// serves the metrics at /metrics until the process is stopped, they are rendered again for every
+// scrape (so runs and submissions of other processes show up right away)
+pub async fn serve(config: &Config, years: &[u16], address: SocketAddr) -> Result<()> {
+    let listener = TcpListener::bind(address)
+        .await
+        .map_err(|e| anyhow!("failed to listen on {}: {}", address, e))?;
+    eprintln!(
+        "serving metrics at http://{}/metrics",
+        listener.local_addr()?
+    );
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+
+        // only the path of the request line matters
+        let mut request = [0; 1024];
+        let length = stream.read(&mut request).await.unwrap_or(0);
+        let request = String::from_utf8_lossy(&request[..length]);
+        let (status, body) = match request.split_whitespace().nth(1) {
+            Some("/metrics") => match render(config, years) {
+                Ok(metrics) => ("200 OK", metrics),
+                Err(e) => ("500 Internal Server Error", format!("{:#}\n", e)),
+            },
+            _ => (
+                "404 Not Found",
+                "the metrics are served at /metrics\n".to_string(),
+            ),
+        };
+
+        // a scraper which went away doesn't stop the server
+        let _ = stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .as_bytes(),
+            )
+            .await;
+    }
+}
@@ -0,0 +1,154 @@
+// reads the capital letters some puzzles draw with lit and dark pixels instead of printing an
+// answer, e.g. "AB" in the font of adventofcode.com
+//
+// .##..###.
+// #..#.#..#
+// #..#.###.
+// ####.#..#
+// #..#.#..#
+// #..#.###.
+
+const HEIGHT: usize = 6;
+
+// the letters known to appear in answers (dark columns around them don't matter)
+const GLYPHS: [(char, [&str; HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+// a line drawn with lit and dark pixels (dark ones are dots or spaces)
+fn is_art(line: &str) -> bool {
+    line.chars().any(is_lit) && line.chars().all(|c| is_lit(c) || c == '.' || c == ' ')
+}
+
+// the text drawn by the lines, if every letter is known
+pub fn decode(lines: &[&str]) -> Option<String> {
+    if lines.len() != HEIGHT {
+        return None;
+    }
+
+    let rows: Vec<Vec<bool>> = lines
+        .iter()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max()?;
+    let lit = |row: usize, column: usize| rows[row].get(column).copied().unwrap_or(false);
+
+    // letters are separated by at least one dark column
+    let mut text = String::new();
+    let mut column = 0;
+    while column < width {
+        if (0..HEIGHT).all(|row| !lit(row, column)) {
+            column += 1;
+            continue;
+        }
+
+        let start = column;
+        while column < width && (0..HEIGHT).any(|row| lit(row, column)) {
+            column += 1;
+        }
+
+        let glyph: Vec<String> = (0..HEIGHT)
+            .map(|row| {
+                (start..column)
+                    .map(|column| if lit(row, column) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let (letter, _) = GLYPHS.iter().find(|(_, rows)| *rows == glyph[..])?;
+        text.push(*letter);
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+// the blocks of art in the output with the text they draw, as (first line, text)
+pub fn find(output: &str) -> Vec<(usize, String)> {
+    let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    let mut found = Vec::new();
+    let mut start = 0;
+
+    while start < lines.len() {
+        let end = start
+            + lines[start..]
+                .iter()
+                .take_while(|line| is_art(line))
+                .count();
+
+        if let Some(text) = decode(&lines[start..end]) {
+            found.push((start, text));
+        }
+        start = end.max(start + 1);
+    }
+
+    found
+}
+
+// the output with every block of art replaced by the line of text it draws (a label on the line
+// before it, e.g. "Part 2:", is kept in front of the text)
+pub fn replace(output: &str) -> String {
+    let found = find(output);
+    if found.is_empty() {
+        return output.to_string();
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut skip = 0;
+
+    for (index, line) in output.lines().enumerate() {
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+
+        match found.iter().find(|(start, _)| *start == index) {
+            Some((_, text)) => {
+                match lines.last_mut() {
+                    Some(label) if label.trim_end().ends_with(':') => {
+                        *label = format!("{} {}", label.trim_end(), text)
+                    }
+                    _ => lines.push(text.clone()),
+                }
+                skip = HEIGHT - 1;
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    let mut replaced = lines.join("\n");
+    if output.ends_with('\n') {
+        replaced.push('\n');
+    }
+    replaced
+}
+
+// a single answer drawn as art (e.g. a multi-line json string) as the text it draws
+pub fn decode_answer(answer: String) -> String {
+    let lines: Vec<&str> = answer.lines().map(str::trim_end).collect();
+    let decoded = match lines.iter().all(|line| is_art(line)) {
+        true => decode(&lines),
+        false => None,
+    };
+
+    decoded.unwrap_or(answer)
+}
@@ -0,0 +1,384 @@
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+use crate::ocr;
+
+// how the answers are extracted from the output of a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputStrategy {
+    // one line = part 1, two lines = part 1 and part 2 (anything else is not submitted),
+    // labeled lines take precedence, so debug output doesn't get in the way of them
+    #[default]
+    NewlineCount,
+    // lines like "Part 1: 12345" anywhere in the output
+    Labeled,
+    // a json object like {"part1": "...", "part2": "...", "time_ms": 1.5} on a line of its own,
+    // the optional time is the one the solution measured itself and is shown instead of the runtime
+    // (per part with "part1_ms" and "part2_ms")
+    Json,
+    // the whole (trimmed) output is the answer to part 1
+    SinglePart,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+// the time a solution measured itself (json protocol)
+pub struct ReportedTime {
+    pub total: Duration,
+    // the time of each part, if they were measured separately
+    pub parts: [Option<Duration>; 2],
+}
+
+impl Answers {
+    pub fn is_empty(&self) -> bool {
+        self.part1.is_none() && self.part2.is_none()
+    }
+
+    // keeps only the answer of the given part (both if none is given)
+    pub fn select(self, part: Option<u8>) -> Self {
+        match part {
+            Some(1) => Self {
+                part2: None,
+                ..self
+            },
+            Some(_) => Self {
+                part1: None,
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    // the answers paired with their part number
+    pub fn parts(&self) -> [(u8, Option<&str>); 2] {
+        [(1, self.part1.as_deref()), (2, self.part2.as_deref())]
+    }
+
+    // the answers as they are submitted (answers which end up empty are dropped)
+    pub fn normalize(&self, normalization: &Normalization) -> Self {
+        let normalize = |answer: &Option<String>| {
+            answer
+                .as_deref()
+                .map(|answer| normalization.apply(answer))
+                .filter(|answer| !answer.is_empty())
+        };
+
+        Self {
+            part1: normalize(&self.part1),
+            part2: normalize(&self.part2),
+        }
+    }
+}
+
+// how answers are cleaned up before they are submitted, e.g.
+//
+// normalize:
+//   thousands_separators: true
+//   lowercase: true
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Normalization {
+    // whitespace around the answer
+    pub trim: bool,
+    // carriage returns of windows line endings (e.g. of multi-line answers)
+    pub line_endings: bool,
+    // separators between the digit groups of numbers, e.g. "1,234,567" or "1_234_567"
+    pub thousands_separators: bool,
+    pub lowercase: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self {
+            trim: true,
+            line_endings: true,
+            thousands_separators: false,
+            lowercase: false,
+        }
+    }
+}
+
+impl Normalization {
+    pub fn apply(&self, answer: &str) -> String {
+        let mut answer = answer.to_string();
+
+        if self.line_endings {
+            answer = answer.replace("\r\n", "\n").replace('\r', "");
+        }
+        if self.trim {
+            answer = answer.trim().to_string();
+        }
+        // only whole numbers are touched, other answers might contain the separators on purpose
+        if self.thousands_separators
+            && Regex::new(r"^-?\d{1,3}(?:[,_' ]\d{3})+$")
+                .unwrap()
+                .is_match(&answer)
+        {
+            answer.retain(|c| c == '-' || c.is_ascii_digit());
+        }
+        if self.lowercase {
+            answer = answer.to_lowercase();
+        }
+
+        answer
+    }
+}
+
+impl fmt::Display for OutputStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            OutputStrategy::NewlineCount => write!(f, "newline-count"),
+            OutputStrategy::Labeled => write!(f, "labeled"),
+            OutputStrategy::Json => write!(f, "json"),
+            OutputStrategy::SinglePart => write!(f, "single-part"),
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    // e.g. "'123' as part 1 and '456' as part 2"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .parts()
+            .iter()
+            .filter_map(|(part, answer)| Some(format!("'{}' as part {}", (*answer)?, part)))
+            .collect();
+
+        write!(f, "{}", parts.join(" and "))
+    }
+}
+
+// extracts the answers with regex patterns (one per part, the first capture group is the answer,
+// or a single one with "part1" and "part2" groups) if any are configured, else with the output
+// strategy
+pub struct Extractor {
+    strategy: OutputStrategy,
+    patterns: Vec<Regex>,
+}
+
+impl Extractor {
+    pub fn new(strategy: OutputStrategy, patterns: &[String]) -> Result<Self> {
+        if patterns.len() > 2 {
+            return Err(anyhow!(
+                "at most two answer patterns can be given (one per part)"
+            ));
+        }
+
+        Ok(Self {
+            strategy,
+            patterns: patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(&format!("(?m){}", pattern))
+                        .map_err(|e| anyhow!("invalid answer pattern '{}': {}", pattern, e))
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    // answers drawn as ascii art are read as the letters they draw
+    pub fn parse(&self, stdout: &str) -> Option<Answers> {
+        let stdout = &ocr::replace(stdout);
+        let answers = self.parse_text(stdout)?;
+
+        Some(Answers {
+            part1: answers.part1.map(ocr::decode_answer),
+            part2: answers.part2.map(ocr::decode_answer),
+        })
+    }
+
+    fn parse_text(&self, stdout: &str) -> Option<Answers> {
+        if self.patterns.is_empty() {
+            return self.strategy.parse(stdout);
+        }
+
+        // later matches win, like with the labeled strategy
+        let mut answers = Answers::default();
+        let found = |captures: &regex::Captures, name: &str| {
+            captures
+                .name(name)
+                .map(|answer| answer.as_str().trim().to_string())
+        };
+
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            if pattern
+                .capture_names()
+                .flatten()
+                .any(|name| name == "part1" || name == "part2")
+            {
+                for captures in pattern.captures_iter(stdout) {
+                    answers.part1 = found(&captures, "part1").or(answers.part1);
+                    answers.part2 = found(&captures, "part2").or(answers.part2);
+                }
+                continue;
+            }
+
+            let answer = pattern
+                .captures_iter(stdout)
+                .last()
+                .and_then(|captures| captures.get(1).or(captures.get(0)))
+                .map(|answer| answer.as_str().trim().to_string());
+            match index {
+                0 => answers.part1 = answer.or(answers.part1),
+                _ => answers.part2 = answer.or(answers.part2),
+            }
+        }
+
+        (!answers.is_empty()).then_some(answers)
+    }
+
+    // the time the solution reported to have taken (only part of the json protocol), the total
+    // defaults to the sum of the parts
+    pub fn reported_time(&self, stdout: &str) -> Option<ReportedTime> {
+        if self.strategy != OutputStrategy::Json || !self.patterns.is_empty() {
+            return None;
+        }
+
+        let object = parse_json_object(stdout)?;
+        let duration = |key: &str| {
+            object
+                .get(key)?
+                .as_f64()
+                .and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok())
+        };
+
+        let parts = [duration("part1_ms"), duration("part2_ms")];
+        let total = duration("time_ms").or_else(|| {
+            parts
+                .iter()
+                .any(Option::is_some)
+                .then(|| parts.iter().flatten().sum())
+        })?;
+
+        Some(ReportedTime { total, parts })
+    }
+}
+
+impl fmt::Display for Extractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.patterns.is_empty() {
+            write!(f, "{}", self.strategy)
+        } else {
+            write!(f, "answer patterns")
+        }
+    }
+}
+
+impl OutputStrategy {
+    // extracts the answers from the output, returns none if the output doesn't follow the strategy
+    pub fn parse(&self, stdout: &str) -> Option<Answers> {
+        let answers = match *self {
+            OutputStrategy::NewlineCount => parse_labeled(stdout)
+                .filter(|answers| !answers.is_empty())
+                .or_else(|| parse_newline_count(stdout)),
+            OutputStrategy::Labeled => parse_labeled(stdout),
+            OutputStrategy::Json => parse_json(stdout),
+            OutputStrategy::SinglePart => Some(Answers {
+                part1: Some(stdout.trim().to_string()).filter(|answer| !answer.is_empty()),
+                part2: None,
+            }),
+        }?;
+
+        (!answers.is_empty()).then_some(answers)
+    }
+}
+
+// heuristically picks answer candidates from noisy output (numbers or blocks of uppercase letters),
+// falling back to the last non-empty line
+pub fn guess_answers(stdout: &str) -> Option<Answers> {
+    let stdout = &ocr::replace(stdout);
+    let candidate = Regex::new(r"^(-?\d+|[A-Z]{4,12})$").unwrap();
+    let lines: Vec<&str> = stdout
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let candidates: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| candidate.is_match(line))
+        .collect();
+
+    // the answers are usually printed last
+    match candidates[..] {
+        [] => lines.last().map(|line| Answers {
+            part1: Some(line.to_string()),
+            part2: None,
+        }),
+        [part1] => Some(Answers {
+            part1: Some(part1.to_string()),
+            part2: None,
+        }),
+        [.., part1, part2] => Some(Answers {
+            part1: Some(part1.to_string()),
+            part2: Some(part2.to_string()),
+        }),
+    }
+}
+
+fn parse_newline_count(stdout: &str) -> Option<Answers> {
+    // newlines must be:
+    // 1 = first part
+    // 2 = first and second part
+    match stdout.matches('\n').count() {
+        1 => Some(Answers {
+            part1: Some(stdout.trim_end().to_string()),
+            part2: None,
+        }),
+        2 => {
+            let (part1, part2) = stdout.split_once('\n').unwrap();
+            Some(Answers {
+                part1: Some(part1.to_string()),
+                part2: Some(part2.trim_end().to_string()),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_labeled(stdout: &str) -> Option<Answers> {
+    let re = Regex::new(r"(?im)^\s*part\s*(?P<part>[12])\s*[:=]\s*(?P<answer>.+?)\s*$").unwrap();
+    let mut answers = Answers::default();
+
+    // later lines win, so a final answer can follow intermediate results
+    for captures in re.captures_iter(stdout) {
+        let answer = Some(captures["answer"].to_string());
+        match &captures["part"] {
+            "1" => answers.part1 = answer,
+            _ => answers.part2 = answer,
+        }
+    }
+
+    Some(answers)
+}
+
+fn parse_json(stdout: &str) -> Option<Answers> {
+    // answers can be json strings or numbers
+    let value_to_string = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+
+    let object = parse_json_object(stdout)?;
+    Some(Answers {
+        part1: object.get("part1").and_then(value_to_string),
+        part2: object.get("part2").and_then(value_to_string),
+    })
+}
+
+// the last line of the output that is a json object
+fn parse_json_object(stdout: &str) -> Option<serde_json::Map<String, serde_json::Value>> {
+    stdout
+        .lines()
+        .rev()
+        .find_map(|line| serde_json::from_str(line.trim()).ok())
+}
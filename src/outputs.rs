@@ -0,0 +1,126 @@
+use anyhow::Result;
+use std::{fmt::Write, fs, path::PathBuf, process::Output};
+
+use crate::{args::Args, paths, storage, theme};
+
+// the output of the latest run of every project and the one of its latest successful run, for the
+// menu shown after a failed run
+fn dir(args: &Args) -> Result<PathBuf> {
+    let mut project = args
+        .language
+        .map_or("project".to_string(), |language| language.to_string());
+    if args.redo {
+        project.push_str("-redo");
+    }
+
+    Ok(paths::data_dir()?
+        .join("outputs")
+        .join(args.year.unwrap().to_string())
+        .join(format!("{:0>2}", args.day.unwrap()))
+        .join(project))
+}
+
+pub fn save(args: &Args, output: &Output) -> Result<()> {
+    let dir = dir(args)?;
+    fs::create_dir_all(&dir)?;
+    storage::write(&dir.join("last.stdout"), &output.stdout)?;
+    storage::write(&dir.join("last.stderr"), &output.stderr)?;
+    Ok(())
+}
+
+// keeps the output of the latest run as the one to compare later runs with
+pub fn keep_success(args: &Args) -> Result<()> {
+    let dir = dir(args)?;
+    if dir.join("last.stdout").exists() {
+        storage::write(
+            &dir.join("success.stdout"),
+            fs::read(dir.join("last.stdout"))?,
+        )?;
+    }
+    Ok(())
+}
+
+pub fn last_stderr(args: &Args) -> Result<Option<String>> {
+    read(args, "last.stderr")
+}
+
+fn read(args: &Args, name: &str) -> Result<Option<String>> {
+    match fs::read_to_string(dir(args)?.join(name)) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// the lines of the latest output which differ from the latest successful one, as removed ("-")
+// and added ("+") lines, none if either output is missing
+pub fn diff(args: &Args) -> Result<Option<String>> {
+    let (Some(success), Some(last)) = (read(args, "success.stdout")?, read(args, "last.stdout")?)
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(diff_lines(
+        &success.lines().collect::<Vec<_>>(),
+        &last.lines().collect::<Vec<_>>(),
+    )))
+}
+
+// differing parts larger than this (their numbers of lines multiplied) are shown as removed and
+// added as a whole
+const MAX_TABLE: usize = 4_000_000;
+
+fn diff_lines(old: &[&str], new: &[&str]) -> String {
+    // the common start and end are skipped, so mostly the differing lines are compared
+    let start = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let end = old[start..]
+        .iter()
+        .rev()
+        .zip(new[start..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let (old, new) = (&old[start..old.len() - end], &new[start..new.len() - end]);
+
+    let mut diff = String::new();
+    if old.len() * new.len() > MAX_TABLE {
+        for line in old {
+            let _ = writeln!(diff, "{}", theme::incorrect(&format!("- {}", line)));
+        }
+        for line in new {
+            let _ = writeln!(diff, "{}", theme::correct(&format!("+ {}", line)));
+        }
+        return diff;
+    }
+
+    // the lengths of the longest common subsequences of the remaining lines
+    let mut table = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i][j] = match old[i] == new[j] {
+                true => table[i + 1][j + 1] + 1,
+                false => table[i + 1][j].max(table[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            let _ = writeln!(diff, "  {}", old[i]);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || i < old.len() && table[i + 1][j] >= table[i][j + 1] {
+            let _ = writeln!(diff, "{}", theme::incorrect(&format!("- {}", old[i])));
+            i += 1;
+        } else {
+            let _ = writeln!(diff, "{}", theme::correct(&format!("+ {}", new[j])));
+            j += 1;
+        }
+    }
+
+    diff
+}
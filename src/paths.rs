@@ -0,0 +1,120 @@
+use anyhow::{Context, Result, anyhow};
+use std::{
+    env, fs,
+    path::{Component, Path, PathBuf},
+};
+
+// everything of the runner lives below $AOC_HOME if it's set (e.g. in containers or on shared
+// machines without a usable home directory), otherwise in the usual directories of the platform
+fn aoc_home() -> Option<PathBuf> {
+    env::var_os("AOC_HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+// the directory "~/" refers to in the config (falling back to $AOC_HOME without a home directory)
+pub fn home() -> Result<PathBuf> {
+    dirs::home_dir()
+        .or_else(aoc_home)
+        .context("could not determine home directory (set AOC_HOME instead)")
+}
+
+pub fn config_file() -> Result<PathBuf> {
+    match aoc_home() {
+        Some(home) => Ok(home.join("config.yaml")),
+        None => Ok(home()?.join(".config").join("aoc").join("config.yaml")),
+    }
+}
+
+// inputs, the submission history and pending submissions
+pub fn data_dir() -> Result<PathBuf> {
+    match aoc_home() {
+        Some(home) => Ok(home.join("data")),
+        None => Ok(dirs::data_dir()
+            .context("could not determine data directory (set AOC_HOME instead)")?
+            .join("aoc")),
+    }
+}
+
+// the state of the runs of a project (e.g. the logs of its output), below a path mirroring the one
+// of the project, so the project itself only holds files worth committing
+pub fn project_state_dir(project_path: &Path) -> Result<PathBuf> {
+    let project: PathBuf = std::path::absolute(project_path)?
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+
+    Ok(data_dir()?.join("projects").join(project))
+}
+
+pub fn cache_dir() -> Result<PathBuf> {
+    match aoc_home() {
+        Some(home) => Ok(home.join("cache")),
+        None => Ok(dirs::cache_dir()
+            .context("could not determine cache directory (set AOC_HOME instead)")?
+            .join("aoc")),
+    }
+}
+
+// the name of the account running the process
+pub fn user() -> Option<String> {
+    env::var("USER")
+        .ok()
+        .or_else(|| env::var("USERNAME").ok())
+        .filter(|user| !user.is_empty())
+}
+
+// a directory for the temporary files of the current user, which other users can neither read
+// (the copies of projects and inputs in it) nor squat on
+pub fn temp_dir() -> Result<PathBuf> {
+    let dir = env::temp_dir().join(match user() {
+        Some(user) => format!("aoc-{}", user),
+        None => "aoc".to_string(),
+    });
+    fs::create_dir_all(&dir)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        // only the owner of the directory may change its permissions
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).map_err(|e| {
+            anyhow!(
+                "'{}' can't be used for temporary files: {} (it might belong to another user)",
+                dir.display(),
+                e
+            )
+        })?;
+    }
+
+    Ok(dir)
+}
+
+// makes the file readable and writable by its owner only (a no-op where there are no modes)
+pub fn restrict_to_owner(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+// whether users other than the owner can read the file (always false where there are no modes)
+pub fn readable_by_others(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o044 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        false
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::{clock, paths, storage};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingSubmission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub reason: String,
+    pub queued_at: String,
+}
+
+// answers which could not be submitted (e.g. because of a network error)
+// and are kept around to be retried later with "aoc pending submit"
+#[derive(Serialize, Deserialize, Default)]
+pub struct PendingQueue {
+    pub submissions: Vec<PendingSubmission>,
+}
+
+impl PendingQueue {
+    fn path() -> Result<PathBuf> {
+        Ok(paths::data_dir()?.join("pending.yaml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read pending queue '{}'", path.display()))?;
+        serde_yml::from_str(&content)
+            .with_context(|| format!("failed to parse pending queue '{}'", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        storage::write(&path, serde_yml::to_string(self)?)
+            .with_context(|| format!("failed to write pending queue '{}'", path.display()))
+    }
+
+    // queue an answer, replacing an older pending answer for the same part
+    pub fn push(&mut self, year: u16, day: u8, part: u8, answer: &str, reason: &str) {
+        self.submissions
+            .retain(|s| !(s.year == year && s.day == day && s.part == part));
+
+        self.submissions.push(PendingSubmission {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            reason: reason.to_string(),
+            queued_at: clock::now().to_rfc3339(),
+        });
+    }
+
+    // drops the pending answer of the part (e.g. once another answer has been judged), returns
+    // whether there was one
+    pub fn remove(&mut self, year: u16, day: u8, part: u8) -> bool {
+        let count = self.submissions.len();
+        self.submissions
+            .retain(|s| !(s.year == year && s.day == day && s.part == part));
+        self.submissions.len() != count
+    }
+}
@@ -0,0 +1,150 @@
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::{fs, path::PathBuf, process::Command};
+
+use crate::paths;
+
+// the hardware events counted for a run (e.g. to compare variants of a solution beyond its time)
+const EVENTS: [&str; 4] = ["instructions", "cycles", "cache-misses", "branch-misses"];
+
+// the counters of a single run, events the cpu (or the permissions) don't allow are missing
+#[derive(Debug, Default, Serialize)]
+pub struct Counters {
+    pub instructions: Option<u64>,
+    pub cycles: Option<u64>,
+    pub cache_misses: Option<u64>,
+    pub branch_misses: Option<u64>,
+}
+
+impl std::fmt::Display for Counters {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let counters = [
+            ("instructions", self.instructions),
+            ("cycles", self.cycles),
+            ("cache misses", self.cache_misses),
+            ("branch misses", self.branch_misses),
+        ];
+
+        let counted = counters
+            .iter()
+            .map(|(name, count)| match count {
+                Some(count) => format!("{}: {}", name, group_digits(*count)),
+                None => format!("{}: not counted", name),
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", counted.join(", "))
+    }
+}
+
+// 1234567 -> "1,234,567"
+fn group_digits(count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+// a run of the solution under "perf stat", whose counters are written to a temporary file
+pub struct Recording {
+    output: PathBuf,
+}
+
+impl Recording {
+    pub fn new() -> Result<Self> {
+        if !cfg!(target_os = "linux") {
+            return Err(anyhow!(
+                "perfstat mode requires perf, which is only available on linux"
+            ));
+        }
+        if Command::new("perf").arg("--version").output().is_err() {
+            return Err(anyhow!("perfstat mode requires perf to be installed"));
+        }
+
+        Ok(Self {
+            output: paths::temp_dir()?.join(format!("perfstat-{}.csv", std::process::id())),
+        })
+    }
+
+    // the command running the given one under "perf stat" (with the same environment and directory)
+    pub fn wrap(&self, command: &Command) -> Command {
+        let mut wrapped = Command::new("perf");
+        wrapped
+            .args(["stat", "-x", ",", "-e", &EVENTS.join(",")])
+            .arg("-o")
+            .arg(&self.output)
+            .arg("--")
+            .arg(command.get_program())
+            .args(command.get_args());
+
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => wrapped.env(key, value),
+                None => wrapped.env_remove(key),
+            };
+        }
+        if let Some(dir) = command.get_current_dir() {
+            wrapped.current_dir(dir);
+        }
+
+        wrapped
+    }
+
+    // the counters of the last run (earlier ones, e.g. warm-up runs, are overwritten by it)
+    pub fn counters(&self) -> Result<Counters> {
+        let output = fs::read_to_string(&self.output)
+            .map_err(|e| anyhow!("failed to read the output of perf: {}", e))?;
+        Ok(parse(&output))
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.output);
+    }
+}
+
+// the csv output of "perf stat -x ,", e.g.
+//
+// # started on Mon Dec  9 06:00:00 2024
+//
+// 1234567,,instructions:u,1000000,100.00,,
+// <not supported>,,cache-misses:u,0,100.00,,
+//
+// the names of the events get suffixes for their privilege level (or a prefix on hybrid cpus,
+// e.g. "cpu_core/instructions/u"), whose counts are added up
+fn parse(output: &str) -> Counters {
+    let mut counters = Counters::default();
+
+    for line in output.lines().filter(|line| !line.starts_with('#')) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let (Some(value), Some(event)) = (fields.first(), fields.get(2)) else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse::<u64>() else {
+            continue;
+        };
+
+        let event = event
+            .trim()
+            .trim_start_matches("cpu_core/")
+            .trim_start_matches("cpu_atom/");
+        let event = event.split([':', '/']).next().unwrap_or(event);
+        let counter = match event {
+            "instructions" => &mut counters.instructions,
+            "cycles" => &mut counters.cycles,
+            "cache-misses" => &mut counters.cache_misses,
+            "branch-misses" => &mut counters.branch_misses,
+            _ => continue,
+        };
+        *counter = Some(counter.unwrap_or(0) + value);
+    }
+
+    counters
+}
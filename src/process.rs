@@ -0,0 +1,234 @@
+use anyhow::Result;
+use regex::Regex;
+use std::{
+    io::{self, Read, Write},
+    process::{Child, Command, ExitStatus, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::format_duration;
+
+// a timeout like "60s", "2m", "1h 30m" or "500ms" (plain numbers are seconds)
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    if let Ok(seconds) = s.trim().parse::<u64>() {
+        return Ok(Duration::from_secs(seconds));
+    }
+
+    let re = Regex::new(r"^(?P<amount>\d+)\s*(?P<unit>ms|h|m|s)$").unwrap();
+    let mut total = Duration::ZERO;
+    for part in s.split_whitespace() {
+        let captures = re
+            .captures(part)
+            .ok_or_else(|| format!("invalid timeout: {} (e.g. \"60s\" or \"2m\")", s))?;
+        let amount: u64 = captures["amount"].parse().map_err(|_| "timeout too long")?;
+
+        total += match &captures["unit"] {
+            "ms" => Duration::from_millis(amount),
+            "h" => Duration::from_secs(amount * 60 * 60),
+            "m" => Duration::from_secs(amount * 60),
+            _ => Duration::from_secs(amount),
+        };
+    }
+
+    match total.is_zero() {
+        true => Err(format!(
+            "invalid timeout: {} (it has to be longer than zero)",
+            s
+        )),
+        false => Ok(total),
+    }
+}
+
+// a solution which was killed for running too long
+#[derive(Debug)]
+pub struct TimedOut(pub Duration);
+
+impl std::fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out after {}", format_duration(self.0))
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+// copies everything from the source into the sink while capturing it (a sink which went away,
+// e.g. a closed terminal, only stops the copying)
+fn tee(
+    mut source: impl Read + Send + 'static,
+    mut sink: Option<impl Write + Send + 'static>,
+) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut captured = Vec::new();
+        let mut buffer = [0; 8192];
+
+        loop {
+            let read = source.read(&mut buffer)?;
+            if read == 0 {
+                return Ok(captured);
+            }
+            if let Some(writer) = &mut sink
+                && writer
+                    .write_all(&buffer[..read])
+                    .and_then(|_| writer.flush())
+                    .is_err()
+            {
+                sink = None;
+            }
+            captured.extend_from_slice(&buffer[..read]);
+        }
+    })
+}
+
+// the processes started by the given one (e.g. the solution started by "cargo run"), which
+// would otherwise keep running after it's killed
+#[cfg(target_os = "linux")]
+fn descendants(pid: u32) -> Vec<u32> {
+    let parents: Vec<(u32, u32)> = std::fs::read_dir("/proc")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let child = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+            // the name of the process (in parentheses) may contain spaces
+            let parent = stat
+                .rsplit_once(')')?
+                .1
+                .split_whitespace()
+                .nth(1)?
+                .parse()
+                .ok()?;
+            Some((child, parent))
+        })
+        .collect();
+
+    let mut found = vec![pid];
+    let mut index = 0;
+    while index < found.len() {
+        let parent = found[index];
+        found.extend(
+            parents
+                .iter()
+                .filter(|(_, p)| *p == parent)
+                .map(|(child, _)| *child),
+        );
+        index += 1;
+    }
+
+    found.split_off(1)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn descendants(_: u32) -> Vec<u32> {
+    Vec::new()
+}
+
+fn kill(child: &mut Child) {
+    let descendants = descendants(child.id());
+    let _ = child.kill();
+    let _ = child.wait();
+
+    #[cfg(unix)]
+    for pid in descendants {
+        let _ = Command::new("kill")
+            .args(["-KILL", &pid.to_string()])
+            .output();
+    }
+    #[cfg(not(unix))]
+    let _ = descendants;
+}
+
+// the peak resident memory of a process in bytes, e.g. "12.34 MiB"
+pub fn format_memory(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.2} KiB", bytes as f64 / 1024.0),
+        1_048_576..1_073_741_824 => format!("{:.2} MiB", bytes as f64 / 1_048_576.0),
+        _ => format!("{:.2} GiB", bytes as f64 / 1_073_741_824.0),
+    }
+}
+
+// waits for the child (or only checks whether it exited), along with the peak resident memory of
+// it and the processes it waited for (e.g. the solution started by "cargo run")
+#[cfg(unix)]
+fn wait(child: &mut Child, block: bool) -> io::Result<Option<(ExitStatus, Option<u64>)>> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut status = 0;
+    // plain data, which wait4 fills in
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    let options = if block { 0 } else { libc::WNOHANG };
+
+    loop {
+        // the child is reaped here, std doesn't wait for it again (it's only killed before that)
+        let pid =
+            unsafe { libc::wait4(child.id() as libc::pid_t, &mut status, options, &mut usage) };
+        match pid {
+            0 => return Ok(None),
+            -1 if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => continue,
+            -1 => return Err(io::Error::last_os_error()),
+            _ => break,
+        }
+    }
+
+    // the peak is counted in kilobytes, except on apple's platforms
+    let peak =
+        u64::try_from(usage.ru_maxrss)
+            .ok()
+            .map(|peak| match cfg!(target_vendor = "apple") {
+                true => peak,
+                false => peak * 1024,
+            });
+    Ok(Some((ExitStatus::from_raw(status), peak)))
+}
+
+// the memory isn't measured on other platforms
+#[cfg(not(unix))]
+fn wait(child: &mut Child, block: bool) -> io::Result<Option<(ExitStatus, Option<u64>)>> {
+    match block {
+        true => child.wait().map(|status| Some((status, None))),
+        false => Ok(child.try_wait()?.map(|status| (status, None))),
+    }
+}
+
+// runs the command until it exits or the timeout is over (killing it then), its output is copied
+// to the terminal as it arrives if it's live and captured either way (along with the peak memory
+// of the run, where it's measured)
+pub fn run(
+    command: &mut Command,
+    live: bool,
+    timeout: Option<Duration>,
+) -> Result<(Output, Option<u64>)> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = tee(child.stdout.take().unwrap(), live.then(io::stdout));
+    let stderr = tee(child.stderr.take().unwrap(), live.then(io::stderr));
+
+    let (status, peak_memory) = match timeout {
+        Some(timeout) => {
+            let deadline = Instant::now() + timeout;
+            loop {
+                if let Some(finished) = wait(&mut child, false)? {
+                    break finished;
+                }
+                if Instant::now() >= deadline {
+                    kill(&mut child);
+                    // the output isn't waited for, as leftover processes might still hold on to it
+                    return Err(TimedOut(timeout).into());
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+        None => wait(&mut child, true)?.unwrap(),
+    };
+
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap()?,
+        stderr: stderr.join().unwrap()?,
+    };
+    Ok((output, peak_memory))
+}
@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{editorconfig::EditorConfig, storage::hash};
+
+// the files of the template directory relative to it
+fn template_files(dir: &Path, prefix: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(dir.join(prefix))
+        .with_context(|| format!("failed to read template '{}'", dir.display()))?
+    {
+        let entry = entry?;
+        let relative = prefix.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            files.extend(template_files(dir, &relative)?);
+        } else {
+            files.push(relative);
+        }
+    }
+
+    Ok(files)
+}
+
+// the key of a file in the recorded hashes (always using forward slashes)
+fn key(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// the values of the placeholders in the files of a template, e.g. "{{title}}" or "{{pad day}}"
+pub struct Placeholders {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Placeholders {
+    pub fn new(values: Vec<(&'static str, String)>) -> Self {
+        Self { values }
+    }
+
+    // binary files (everything which isn't utf-8) are left alone
+    fn replace(&self, content: Vec<u8>) -> Vec<u8> {
+        match String::from_utf8(content) {
+            Ok(mut text) => {
+                for (name, value) in &self.values {
+                    text = text.replace(&format!("{{{{{}}}}}", name), value);
+                }
+                text.into_bytes()
+            }
+            Err(e) => e.into_bytes(),
+        }
+    }
+}
+
+// copies the template into the project and returns the hashes of the written files
+pub fn apply(
+    template: &Path,
+    project_path: &Path,
+    placeholders: &Placeholders,
+    style: &EditorConfig,
+) -> Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+
+    for relative in template_files(template, Path::new(""))? {
+        let target = project_path.join(&relative);
+        let content = placeholders.replace(fs::read(template.join(&relative))?);
+        let content = style.format(&target, &content);
+
+        fs::create_dir_all(target.parent().unwrap())?;
+        fs::write(&target, &content)?;
+        hashes.insert(key(&relative), hash(&content));
+    }
+
+    Ok(hashes)
+}
+
+pub struct Reapplied {
+    pub updated: Vec<String>,
+    // files which have been edited since they were scaffolded
+    pub modified: Vec<String>,
+}
+
+// re-applies an updated template, only touching files which are still unchanged since they were
+// scaffolded (or didn't exist yet), and updates the recorded hashes accordingly
+pub fn reapply(
+    template: &Path,
+    project_path: &Path,
+    hashes: &mut BTreeMap<String, String>,
+    placeholders: &Placeholders,
+    style: &EditorConfig,
+) -> Result<Reapplied> {
+    let mut reapplied = Reapplied {
+        updated: Vec::new(),
+        modified: Vec::new(),
+    };
+
+    for relative in template_files(template, Path::new(""))? {
+        let key = key(&relative);
+        let target = project_path.join(&relative);
+        let content = placeholders.replace(fs::read(template.join(&relative))?);
+        let content = style.format(&target, &content);
+
+        let untouched = match fs::read(&target) {
+            Ok(current) => hashes.get(&key) == Some(&hash(&current)),
+            Err(_) => true,
+        };
+
+        if !untouched {
+            reapplied.modified.push(key);
+            continue;
+        }
+
+        if hashes.get(&key) != Some(&hash(&content)) || !target.exists() {
+            fs::create_dir_all(target.parent().unwrap())?;
+            fs::write(&target, &content)?;
+            hashes.insert(key.clone(), hash(&content));
+            reapplied.updated.push(key);
+        }
+    }
+
+    Ok(reapplied)
+}
@@ -0,0 +1,91 @@
+use clap::ValueEnum;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScrambleStrategy {
+    // shuffle the order of all lines
+    ShuffleLines,
+    // consistently remap the digits 1-9 (0 is kept, so no leading zeros appear)
+    Digits,
+    // consistently remap letters (upper and lower case use the same mapping)
+    Letters,
+}
+
+// small xorshift generator, good enough for scrambling inputs
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        });
+
+        // the state must never be zero
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+// applies the strategies (in the given order) to the input
+pub fn scramble(input: &str, strategies: &[ScrambleStrategy], seed: Option<u64>) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = input.to_string();
+
+    for strategy in strategies {
+        output = match strategy {
+            ScrambleStrategy::ShuffleLines => {
+                let mut lines: Vec<&str> = output.lines().collect();
+                rng.shuffle(&mut lines);
+
+                // keep the trailing newline of the input
+                let trailing = if output.ends_with('\n') { "\n" } else { "" };
+                lines.join("\n") + trailing
+            }
+            ScrambleStrategy::Digits => {
+                let mut digits: Vec<char> = ('1'..='9').collect();
+                rng.shuffle(&mut digits);
+
+                output
+                    .chars()
+                    .map(|c| match c {
+                        '1'..='9' => digits[c as usize - '1' as usize],
+                        c => c,
+                    })
+                    .collect()
+            }
+            ScrambleStrategy::Letters => {
+                let mut letters: Vec<u8> = (b'a'..=b'z').collect();
+                rng.shuffle(&mut letters);
+
+                output
+                    .chars()
+                    .map(|c| match c {
+                        'a'..='z' => letters[c as usize - 'a' as usize] as char,
+                        'A'..='Z' => {
+                            letters[c as usize - 'A' as usize].to_ascii_uppercase() as char
+                        }
+                        c => c,
+                    })
+                    .collect()
+            }
+        };
+    }
+
+    output
+}
@@ -0,0 +1,64 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use crate::{theme, web};
+
+// destinations the results of runs are written to as json (in addition to the usual output)
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SinkConfig {
+    // print every result (like "--json" does)
+    pub terminal: bool,
+    // file every result is appended to as a line
+    pub jsonl: Option<String>,
+    // url every result is posted to
+    pub webhook: Option<String>,
+}
+
+fn append(path: &str, result: &serde_json::Value) -> Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", result)?;
+    Ok(())
+}
+
+async fn post(url: &str, result: &serde_json::Value) -> Result<()> {
+    web::throttled(reqwest::Client::new().post(url).json(result).send())
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+// writes the result to every configured sink (and the terminal if requested), a failing sink never
+// fails the run, so it's only reported
+pub async fn emit(config: &SinkConfig, terminal: bool, result: &serde_json::Value) {
+    if terminal || config.terminal {
+        println!("{}", result);
+    }
+
+    let failed = |sink: &str, e: anyhow::Error| {
+        eprintln!(
+            "{}",
+            theme::skipped(&format!("failed to write result to '{}': {}", sink, e))
+        )
+    };
+
+    if let Some(path) = &config.jsonl
+        && let Err(e) = append(path, result)
+    {
+        failed(path, e);
+    }
+    if let Some(url) = &config.webhook
+        && let Err(e) = post(url, result).await
+    {
+        failed(url, e);
+    }
+}
@@ -0,0 +1,48 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+// fnv-1a, stable across platforms and versions (unlike the hasher of the standard library)
+pub fn hash(content: &[u8]) -> String {
+    let hash = content.iter().fold(0xcbf29ce484222325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+// a hidden file next to the given one (on the same file system, so it can be renamed), unique
+// per process
+fn temporary_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+// replaces the file as a whole or not at all: the content is written to a temporary file, which
+// is synced and compared with the content by its checksum before it's renamed to the file, so an
+// interruption (e.g. ctrl-c during a download) or a full disk never leaves a truncated file behind
+pub fn write(path: &Path, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let content = content.as_ref();
+    let temporary = temporary_path(path);
+
+    let written = (|| {
+        let mut file = File::create(&temporary)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+
+        if hash(&fs::read(&temporary)?) != hash(content) {
+            return Err(io::Error::other(format!(
+                "'{}' doesn't contain what was written to it",
+                temporary.display()
+            )));
+        }
+        fs::rename(&temporary, path)
+    })();
+
+    if written.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    written
+}
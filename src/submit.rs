@@ -0,0 +1,273 @@
+use anyhow::{Result, anyhow};
+use colored::ColoredString;
+use regex::Regex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{
+    art,
+    cache::{self, Cache, Resource},
+    config::Config,
+    theme, urls,
+    web::{self, send},
+};
+
+// the full answer of the server to a submission (instead of just correct or not)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Incorrect { wait: Option<String> },
+    TooHigh { wait: Option<String> },
+    TooLow { wait: Option<String> },
+    RateLimited { wait: String },
+    AlreadySolved { matches: Option<bool> },
+}
+
+impl Verdict {
+    pub fn is_correct(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct
+                | Verdict::AlreadySolved {
+                    matches: Some(true)
+                }
+        )
+    }
+
+    pub fn is_incorrect(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect { .. }
+                | Verdict::TooHigh { .. }
+                | Verdict::TooLow { .. }
+                | Verdict::AlreadySolved {
+                    matches: Some(false)
+                }
+        )
+    }
+
+    // whether the answer itself was judged, a rate limited one wasn't looked at and "already
+    // solved" without a recorded answer to compare with is also sent for part 2 before part 1 has
+    // been accepted
+    pub fn is_judged(&self) -> bool {
+        !matches!(
+            self,
+            Verdict::RateLimited { .. } | Verdict::AlreadySolved { matches: None }
+        )
+    }
+
+    // the name used in the submission history
+    pub fn label(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect { .. } => "incorrect",
+            Verdict::TooHigh { .. } => "too_high",
+            Verdict::TooLow { .. } => "too_low",
+            Verdict::RateLimited { .. } => "rate_limited",
+            Verdict::AlreadySolved { .. } => "already_solved",
+        }
+    }
+
+    // the verdict without the time to wait (which only applied when it was given)
+    pub fn without_wait(&self) -> Self {
+        match self {
+            Verdict::Incorrect { .. } => Verdict::Incorrect { wait: None },
+            Verdict::TooHigh { .. } => Verdict::TooHigh { wait: None },
+            Verdict::TooLow { .. } => Verdict::TooLow { wait: None },
+            verdict => verdict.clone(),
+        }
+    }
+
+    // color the submitted answer according to the verdict
+    pub fn colorize(&self, answer: &str) -> ColoredString {
+        if self.is_correct() {
+            theme::correct(answer)
+        } else if self.is_incorrect() {
+            theme::incorrect(answer)
+        } else {
+            theme::skipped(answer)
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wait_suffix = |wait: &Option<String>| {
+            wait.as_ref()
+                .map(|wait| format!(", wait {} before trying again", wait))
+                .unwrap_or_default()
+        };
+
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect { wait } => write!(f, "incorrect{}", wait_suffix(wait)),
+            Verdict::TooHigh { wait } => write!(f, "too high{}", wait_suffix(wait)),
+            Verdict::TooLow { wait } => write!(f, "too low{}", wait_suffix(wait)),
+            Verdict::RateLimited { wait } => write!(f, "rate limited, {} left to wait", wait),
+            Verdict::AlreadySolved { matches } => write!(
+                f,
+                "already solved{}",
+                match matches {
+                    Some(true) => ", answer matches",
+                    Some(false) => ", answer does not match",
+                    None => "",
+                }
+            ),
+        }
+    }
+}
+
+// extracts the answers already accepted for a day from its puzzle page
+pub fn parse_recorded_answers(puzzle_html: &str) -> Vec<String> {
+    Regex::new(r"<p>Your puzzle answer was <code>(?<answer>.*?)</code>.</p>")
+        .unwrap()
+        .captures_iter(puzzle_html)
+        .map(|c| c["answer"].to_string())
+        .collect()
+}
+
+// the title of a puzzle from its page, e.g. "Toboggan Trajectory"
+pub fn parse_title(puzzle_html: &str) -> Option<String> {
+    Regex::new(r"<h2>--- Day \d+: (?<title>.*?) ---</h2>")
+        .unwrap()
+        .captures(puzzle_html)
+        .map(|c| art::decode_entities(&c["title"]))
+}
+
+// the page of a puzzle, preferring a cached copy
+pub async fn puzzle_page(config: &Config, cookie: &str, year: u16, day: u8) -> Result<String> {
+    let cache = Cache::open(&config.cache)?;
+    // the page lists the answers accepted for the account
+    let key = cache::account_key(cookie, &format!("{}-{}", year, day));
+
+    match cache.get(Resource::Puzzle, &key) {
+        Some(puzzle) => Ok(puzzle),
+        None => {
+            let puzzle = web::get(cookie, &urls::puzzle(year, day)).await?;
+            cache.put(Resource::Puzzle, &key, &puzzle)?;
+            Ok(puzzle)
+        }
+    }
+}
+
+// the answers already accepted for a day, from the (cached) puzzle page
+pub async fn accepted_answers(
+    config: &Config,
+    cookie: &str,
+    year: u16,
+    day: u8,
+) -> Result<Vec<String>> {
+    // accepted answers never disappear, so an outdated page at most lacks the newest one
+    Ok(parse_recorded_answers(
+        &puzzle_page(config, cookie, year, day).await?,
+    ))
+}
+
+fn capture_wait(response: &str, pattern: &str) -> Option<String> {
+    Regex::new(pattern)
+        .unwrap()
+        .captures(response)
+        .map(|c| c["time"].to_string())
+}
+
+pub async fn submit_answer(
+    cookie: &str,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict> {
+    let client = Client::new();
+    let uri = urls::answer(year, day);
+    let response = send(
+        cookie,
+        client
+            .post(&uri)
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())]),
+    )
+    .await?;
+
+    if response.contains("That's the right answer!") {
+        Ok(Verdict::Correct)
+    } else if response.contains("You gave an answer too recently") {
+        let wait = capture_wait(&response, r"You have (?<time>.*?) left to wait")
+            .ok_or(anyhow!("cooldown time could not be retrieved"))?;
+        Ok(Verdict::RateLimited { wait })
+    } else if response.contains("That's not the right answer") {
+        let wait = capture_wait(&response, r"wait (?<time>.*?) before trying again");
+
+        Ok(if response.contains("your answer is too high") {
+            Verdict::TooHigh { wait }
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow { wait }
+        } else {
+            Verdict::Incorrect { wait }
+        })
+    } else if response.contains("Did you already complete it?")
+        || response.contains("Both parts of this puzzle are complete!")
+    {
+        // compare against the answer recorded on the puzzle page
+        let puzzle = web::get(cookie, &urls::puzzle(year, day)).await?;
+
+        Ok(Verdict::AlreadySolved {
+            matches: parse_recorded_answers(&puzzle)
+                .get(part as usize - 1)
+                .map(|recorded| recorded == answer),
+        })
+    } else {
+        fallback_verdict(cookie, year, day, part, answer, &response).await
+    }
+}
+
+// the text of the message the server responded with (inside the <article> element)
+fn article_text(response: &str) -> Option<String> {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>")
+        .unwrap()
+        .captures(response)?
+        .get(1)?
+        .as_str()
+        .to_string();
+
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(&article, "");
+    Some(text.split_whitespace().collect::<Vec<&str>>().join(" "))
+}
+
+// used when the response doesn't contain any of the known phrases (e.g. because the wording changed):
+// 1. check whether the answer has been recorded on the puzzle page
+// 2. fall back to keyword matching on the response message
+async fn fallback_verdict(
+    cookie: &str,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+    response: &str,
+) -> Result<Verdict> {
+    if let Ok(puzzle) = web::get(cookie, &urls::puzzle(year, day)).await
+        && parse_recorded_answers(&puzzle).get(part as usize - 1) == Some(&answer.to_string())
+    {
+        return Ok(Verdict::Correct);
+    }
+
+    let message = article_text(response).ok_or(anyhow!("unknown response to submission"))?;
+    let lower = message.to_lowercase();
+    let wait = capture_wait(&lower, r"wait (?<time>\S+(?: \S+)?) before");
+
+    if lower.contains("too recently") {
+        Ok(Verdict::RateLimited {
+            wait: capture_wait(&lower, r"have (?<time>.*?) left").unwrap_or(message),
+        })
+    } else if lower.contains("too high") {
+        Ok(Verdict::TooHigh { wait })
+    } else if lower.contains("too low") {
+        Ok(Verdict::TooLow { wait })
+    } else if lower.contains("not the right answer") || lower.contains("incorrect") {
+        Ok(Verdict::Incorrect { wait })
+    } else if lower.contains("right answer") {
+        Ok(Verdict::Correct)
+    } else {
+        Err(anyhow!("unknown response to submission: {}", message))
+    }
+}
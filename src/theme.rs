@@ -0,0 +1,194 @@
+use anyhow::{Result, anyhow};
+use colored::{Color, ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    #[default]
+    Default,
+    // blue and orange instead of green and red
+    Colorblind,
+    // no colors and only ascii symbols (for dumb terminals)
+    Ascii,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct StyleConfig {
+    // a color name ("green", "bright blue"), a hex color ("#e69f00"), "dimmed" or "none"
+    pub color: Option<String>,
+    // prefix used in listings (e.g. by the check mode)
+    pub symbol: Option<String>,
+}
+
+// colors and symbols of the output, overrides are applied on top of the preset
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub preset: Preset,
+    pub correct: StyleConfig,
+    pub incorrect: StyleConfig,
+    // skipped checks, answers which couldn't be judged and other warnings
+    pub skipped: StyleConfig,
+    // color of timings
+    pub timing: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum Paint {
+    None,
+    Dimmed,
+    Color(Color),
+}
+
+impl Paint {
+    fn parse(color: &str) -> Result<Self> {
+        let invalid = || anyhow!("invalid theme color: {}", color);
+
+        match color.to_lowercase().as_str() {
+            "none" => Ok(Paint::None),
+            "dimmed" => Ok(Paint::Dimmed),
+            hex if hex.starts_with('#') && hex.len() == 7 => {
+                let channel =
+                    |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+                Ok(Paint::Color(Color::TrueColor {
+                    r: channel(1)?,
+                    g: channel(3)?,
+                    b: channel(5)?,
+                }))
+            }
+            name => name.parse().map(Paint::Color).map_err(|_| invalid()),
+        }
+    }
+
+    fn apply(&self, text: &str) -> ColoredString {
+        match *self {
+            Paint::None => text.normal(),
+            Paint::Dimmed => text.dimmed(),
+            Paint::Color(color) => text.color(color),
+        }
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Paint {
+    Paint::Color(Color::TrueColor { r, g, b })
+}
+
+struct Style {
+    paint: Paint,
+    symbol: String,
+}
+
+impl Style {
+    fn new(config: &StyleConfig, paint: Paint, symbol: &str) -> Result<Self> {
+        Ok(Self {
+            paint: config
+                .color
+                .as_deref()
+                .map(Paint::parse)
+                .transpose()?
+                .unwrap_or(paint),
+            symbol: config.symbol.clone().unwrap_or(symbol.to_string()),
+        })
+    }
+}
+
+struct Theme {
+    correct: Style,
+    incorrect: Style,
+    skipped: Style,
+    timing: Paint,
+    ascii: bool,
+}
+
+impl Theme {
+    fn new(config: &ThemeConfig) -> Result<Self> {
+        let ((correct, incorrect, skipped), symbols) = match config.preset {
+            Preset::Default => (
+                (
+                    Paint::Color(Color::Green),
+                    Paint::Color(Color::Red),
+                    Paint::Color(Color::Yellow),
+                ),
+                ("✓", "✗", "-"),
+            ),
+            // the okabe-ito palette, distinguishable with all common types of color blindness
+            Preset::Colorblind => (
+                (
+                    rgb(0x56, 0xb4, 0xe9),
+                    rgb(0xe6, 0x9f, 0x00),
+                    rgb(0xcc, 0x79, 0xa7),
+                ),
+                ("✓", "✗", "-"),
+            ),
+            Preset::Ascii => ((Paint::None, Paint::None, Paint::None), ("+", "x", "-")),
+        };
+
+        Ok(Self {
+            correct: Style::new(&config.correct, correct, symbols.0)?,
+            incorrect: Style::new(&config.incorrect, incorrect, symbols.1)?,
+            skipped: Style::new(&config.skipped, skipped, symbols.2)?,
+            timing: match &config.timing {
+                Some(color) => Paint::parse(color)?,
+                None if config.preset == Preset::Ascii => Paint::None,
+                None => Paint::Dimmed,
+            },
+            ascii: config.preset == Preset::Ascii,
+        })
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+fn theme() -> &'static Theme {
+    THEME.get_or_init(|| Theme::new(&ThemeConfig::default()).unwrap())
+}
+
+// sets the theme for the rest of the process
+pub fn configure(config: &ThemeConfig) -> Result<()> {
+    let theme = Theme::new(config)?;
+
+    // dumb terminals don't understand any escape codes (not even bold or dimmed text)
+    if theme.ascii {
+        colored::control::set_override(false);
+    }
+
+    let _ = THEME.set(theme);
+    Ok(())
+}
+
+// whether only ascii characters should be printed
+pub fn ascii() -> bool {
+    theme().ascii
+}
+
+pub fn correct(text: &str) -> ColoredString {
+    theme().correct.paint.apply(text)
+}
+
+pub fn incorrect(text: &str) -> ColoredString {
+    theme().incorrect.paint.apply(text)
+}
+
+pub fn skipped(text: &str) -> ColoredString {
+    theme().skipped.paint.apply(text)
+}
+
+pub fn timing(text: &str) -> ColoredString {
+    theme().timing.apply(text)
+}
+
+// the colored symbols of the statuses (correct, incorrect and skipped)
+pub fn correct_symbol() -> ColoredString {
+    correct(&theme().correct.symbol)
+}
+
+pub fn incorrect_symbol() -> ColoredString {
+    incorrect(&theme().incorrect.symbol)
+}
+
+pub fn skipped_symbol() -> ColoredString {
+    skipped(&theme().skipped.symbol)
+}
@@ -0,0 +1,42 @@
+use std::sync::OnceLock;
+
+// the host every url points to, can be swapped for a mirror (see the network config)
+static BASE_URL: OnceLock<String> = OnceLock::new();
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// sets the base url for the rest of the process
+pub fn configure(base_url: &str) {
+    let _ = BASE_URL.set(base_url.trim_end_matches('/').to_string());
+}
+
+fn base() -> &'static str {
+    BASE_URL.get().map_or(DEFAULT_BASE_URL, String::as_str)
+}
+
+// the list of all events with the earned stars per year
+pub fn events() -> String {
+    format!("{}/events", base())
+}
+
+// the overview page of a year containing the calendar
+pub fn calendar(year: u16) -> String {
+    format!("{}/{}", base(), year)
+}
+
+pub fn puzzle(year: u16, day: u8) -> String {
+    format!("{}/{}/day/{}", base(), year, day)
+}
+
+pub fn input(year: u16, day: u8) -> String {
+    format!("{}/input", puzzle(year, day))
+}
+
+pub fn answer(year: u16, day: u8) -> String {
+    format!("{}/answer", puzzle(year, day))
+}
+
+// the personal solve times of a year
+pub fn personal_times(year: u16) -> String {
+    format!("{}/{}/leaderboard/self", base(), year)
+}
@@ -0,0 +1,56 @@
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt, sync::OnceLock};
+
+use crate::theme;
+
+// the warnings which can be suppressed, by their id in the config ("allow") or with "--allow"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Warning {
+    // the config holding the cookie is readable by other users
+    ReadableCookie,
+    // no cookie is configured, so inputs aren't downloaded and answers aren't submitted
+    NoCookie,
+    // a cookie is configured, but the answers won't be submitted (e.g. for an unofficial input)
+    UnusedCookie,
+    // the first answer of a day is about to be submitted without a run on an example beforehand
+    MissingExample,
+    // the solution is built without optimizations, so its timings aren't representative
+    DebugBuild,
+    // the selected day differs from the one of the current directory
+    DirectoryMismatch,
+    // an answer contradicts a bound hinted at by an earlier verdict
+    ContradictedBound,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_possible_value().unwrap().get_name())
+    }
+}
+
+static ALLOWED: OnceLock<HashSet<Warning>> = OnceLock::new();
+
+// suppresses the given warnings for the rest of the process
+pub fn configure<'a>(allowed: impl IntoIterator<Item = &'a Warning>) {
+    let _ = ALLOWED.set(allowed.into_iter().copied().collect());
+}
+
+// prints the warning with its id (so it's known how to suppress it), unless it's allowed
+pub fn warn(warning: Warning, message: &str) {
+    if ALLOWED
+        .get()
+        .is_some_and(|allowed| allowed.contains(&warning))
+    {
+        return;
+    }
+
+    let message = format!("warning: {} [{}]", message, warning);
+    match warning {
+        // running another day than the one of the directory could waste a submission
+        Warning::DirectoryMismatch => eprintln!("{}", theme::incorrect(&message).bold()),
+        _ => eprintln!("{}", theme::skipped(&message)),
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use reqwest::{Client, RequestBuilder, header::COOKIE};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::OnceLock, time::Duration};
+use tokio::{
+    sync::{Mutex, Semaphore},
+    time::Instant,
+};
+
+use crate::{metrics, urls};
+
+// limits obeyed by every network operation (to stay within the automation expectations of aoc)
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct NetworkConfig {
+    // maximum number of requests in flight at the same time
+    pub max_concurrent: usize,
+    // minimum time between the start of two requests
+    pub min_delay_ms: u64,
+    // host all requests are sent to (e.g. a mirror)
+    pub base_url: String,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 2,
+            min_delay_ms: 1000,
+            base_url: urls::DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+struct Limiter {
+    permits: Semaphore,
+    min_delay: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Limiter {
+    fn new(config: &NetworkConfig) -> Self {
+        Self {
+            permits: Semaphore::new(config.max_concurrent.max(1)),
+            min_delay: Duration::from_millis(config.min_delay_ms),
+            last_request: Mutex::new(None),
+        }
+    }
+}
+
+static LIMITER: OnceLock<Limiter> = OnceLock::new();
+
+// sets the limits for the rest of the process (has no effect once a request has been made)
+pub fn configure(config: &NetworkConfig) {
+    let _ = LIMITER.set(Limiter::new(config));
+}
+
+// runs the network operation once the configured limits allow it
+pub async fn throttled<T>(operation: impl Future<Output = T>) -> T {
+    let limiter = LIMITER.get_or_init(|| Limiter::new(&NetworkConfig::default()));
+
+    let _permit = limiter.permits.acquire().await.unwrap();
+    {
+        // the lock is held while waiting, so requests are started one after another
+        let mut last_request = limiter.last_request.lock().await;
+        if let Some(last_request) = *last_request {
+            tokio::time::sleep_until(last_request + limiter.min_delay).await;
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    operation.await
+}
+
+// sends the request authenticated with the session cookie and returns the response body
+pub async fn send(cookie: &str, request: RequestBuilder) -> Result<String> {
+    let (client, request) = request
+        .header(COOKIE, format!("session={}", cookie))
+        .build_split();
+    let request = request?;
+    let path = request.url().path().to_string();
+
+    // the latency doesn't include the time waited for the limits
+    let (response, start) = throttled(async {
+        let start = Instant::now();
+        (client.execute(request).await, start)
+    })
+    .await;
+
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            metrics::record_request(&path, "error", start.elapsed());
+            return Err(e.into());
+        }
+    };
+    let status = response.status();
+    let body = response.text().await;
+    metrics::record_request(&path, status.as_str(), start.elapsed());
+
+    if !status.is_success() {
+        return Err(anyhow!("request failed: {}", status));
+    }
+
+    Ok(body?)
+}
+
+pub async fn get(cookie: &str, uri: &str) -> Result<String> {
+    send(cookie, Client::new().get(uri)).await
+}
+
+// the earned stars per year according to the events page, e.g.
+//
+// <div class="eventlist-event"><a href="/2020">[2020]</a> <span class="star-count">50*</span></div>
+pub async fn stars(cookie: &str) -> Result<HashMap<u16, u8>> {
+    let event = Regex::new(r"\[(?P<year>\d{4})\]</a>(?:.*?(?P<stars>\d+)\*)?").unwrap();
+    let page = get(cookie, &urls::events()).await?;
+
+    Ok(page
+        .lines()
+        .filter(|line| line.contains("eventlist-event"))
+        .filter_map(|line| event.captures(line))
+        .filter_map(|captures| {
+            let year = captures["year"].parse().ok()?;
+            let stars = captures
+                .name("stars")
+                .map_or(Some(0), |stars| stars.as_str().parse().ok())?;
+            Some((year, stars))
+        })
+        .collect())
+}